@@ -0,0 +1,77 @@
+use crate::application_command::i18n::Locales;
+use crate::http::HttpClient;
+use twilight_model::application::interaction::application_command_autocomplete::{
+    ApplicationCommandAutocompleteDataOption, ApplicationCommandAutocompleteDataOptionType,
+};
+use twilight_model::application::interaction::ApplicationCommandAutocomplete;
+use worker::Env;
+
+/**
+Context for Application Command Autocomplete.
+**/
+pub struct AutocompleteContext {
+    pub interaction: Box<ApplicationCommandAutocomplete>,
+    pub locale: Locales,
+    pub guild_locale: Option<Locales>,
+    pub env: Env,
+    pub ctx: worker::Context,
+    pub http: HttpClient,
+}
+
+impl AutocompleteContext {
+    pub fn new(
+        interaction: Box<ApplicationCommandAutocomplete>,
+        env: Env,
+        ctx: worker::Context,
+        http: HttpClient,
+    ) -> Self {
+        Self {
+            locale: Locales::from(interaction.locale.as_str()),
+            guild_locale: interaction.guild_locale.as_deref().map(Locales::from),
+            interaction,
+            env,
+            ctx,
+            http,
+        }
+    }
+}
+
+/**
+Returns the option the user is typing.
+**/
+pub fn get_focused_option(
+    options: &[ApplicationCommandAutocompleteDataOption],
+) -> Option<ApplicationCommandAutocompleteDataOption> {
+    options.iter().find(|x| x.focused).cloned().or_else(|| {
+        options
+            .iter()
+            .filter(|x| {
+                matches!(
+                    x.kind,
+                    ApplicationCommandAutocompleteDataOptionType::SubCommand
+                        | ApplicationCommandAutocompleteDataOptionType::SubCommandGroup
+                )
+            })
+            .find_map(|x| get_focused_option(&x.options))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_focused_option;
+    use twilight_model::application::interaction::application_command_autocomplete::ApplicationCommandAutocompleteDataOption;
+
+    #[test]
+    fn test_focused_in_subcommand() {
+        let options = serde_json::from_str::<Vec<ApplicationCommandAutocompleteDataOption>>(
+            r#"[{"type":1,"name":"animal","options":[
+                {"type":4,"name":"count","value":"3"},
+                {"type":3,"name":"name","value":"ca","focused":true}
+            ]}]"#,
+        )
+        .unwrap();
+        let focused = get_focused_option(&options).unwrap();
+        assert_eq!(focused.name, "name");
+        assert_eq!(focused.value.unwrap(), "ca");
+    }
+}
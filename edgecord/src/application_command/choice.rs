@@ -1,5 +1,6 @@
 use crate::application_command::I18nMap;
 use serde::{Serialize, Serializer};
+use twilight_model::application::command::{CommandOptionChoice, Number};
 
 #[derive(Debug, Clone)]
 pub enum ChoiceValue {
@@ -31,3 +32,35 @@ pub struct Choice {
     pub i18n_names: I18nMap,
     pub value: ChoiceValue,
 }
+
+impl From<Choice> for CommandOptionChoice {
+    fn from(choice: Choice) -> Self {
+        let name = choice.name;
+        let name_localizations = choice.i18n_names.map(|map| {
+            map.into_iter()
+                .filter_map(|(locale, value)| {
+                    serde_json::to_value(locale)
+                        .ok()
+                        .and_then(|x| x.as_str().map(|x| (x.to_string(), value)))
+                })
+                .collect()
+        });
+        match choice.value {
+            ChoiceValue::String(value) => CommandOptionChoice::String {
+                name,
+                name_localizations,
+                value,
+            },
+            ChoiceValue::Integer(value) => CommandOptionChoice::Int {
+                name,
+                name_localizations,
+                value,
+            },
+            ChoiceValue::Float(value) => CommandOptionChoice::Number {
+                name,
+                name_localizations,
+                value: Number(value),
+            },
+        }
+    }
+}
@@ -1,10 +1,14 @@
-use crate::application_command::i18n::Locales;
+use crate::application_command::deferred::{run_deferred, DeferOptions};
+use crate::application_command::i18n::{Catalog, Locales};
 use crate::application_command::FromCommandOptionValue;
 use crate::builder::InteractionResponseBuilder;
-use crate::http::HttpClient;
+use crate::http::{Followup, HttpClient};
+use crate::modal::Modal;
 use crate::InteractionResponse;
 use std::future::Future;
+use std::rc::Rc;
 use twilight_model::application::interaction::ApplicationCommand;
+use twilight_model::channel::Message;
 use twilight_model::http::interaction::InteractionResponseType;
 use worker::Env;
 
@@ -13,10 +17,15 @@ Context for ChatInput Command.
 **/
 pub struct ChatInputCommandContext {
     pub interaction: Box<ApplicationCommand>,
+    /// The locale of the user.
     pub locale: Locales,
+    /// The preferred locale of the guild. It is None in DMs.
+    pub guild_locale: Option<Locales>,
     pub env: Env,
     pub ctx: worker::Context,
     pub http: HttpClient,
+    pub catalog: Rc<Catalog>,
+    pub defer_options: Rc<DeferOptions>,
 }
 
 impl ChatInputCommandContext {
@@ -28,14 +37,72 @@ impl ChatInputCommandContext {
     ) -> Self {
         Self {
             interaction: interaction.clone(),
-            locale: serde_json::from_str::<Locales>(&*interaction.locale).unwrap_or(Locales::EnUS),
+            locale: Locales::from(interaction.locale.as_str()),
+            guild_locale: interaction.guild_locale.as_deref().map(Locales::from),
             env,
             ctx,
             http,
+            catalog: Rc::new(Catalog::default()),
+            defer_options: Rc::new(DeferOptions::default()),
         }
     }
 
-    pub fn get_option<T>(interaction: Box<ApplicationCommand>, name: &str) -> T
+    /**
+    The locale of the guild, or the locale of the user in DMs.
+    **/
+    pub fn guild_or_user_locale(&self) -> &Locales {
+        self.guild_locale.as_ref().unwrap_or(&self.locale)
+    }
+
+    /**
+    Negotiate the locale of the user with the catalog and its fallback chain.
+    **/
+    pub fn negotiate_locale(&self) -> Locales {
+        self.catalog
+            .negotiate(&self.locale)
+            .unwrap_or_else(|| self.locale.clone())
+    }
+
+    /**
+    Negotiate the locale of the guild with the catalog and its fallback chain.
+    **/
+    pub fn negotiate_guild_locale(&self) -> Locales {
+        let locale = self.guild_or_user_locale();
+        self.catalog
+            .negotiate(locale)
+            .unwrap_or_else(|| locale.clone())
+    }
+
+    /**
+    Translate the key of the catalog into the locale of the user. Use it for ephemeral messages.
+    **/
+    pub fn translate(&self, key: &str) -> String {
+        self.catalog.translate(&self.locale, key)
+    }
+
+    /**
+    Translate the key and replace `{name}` placeholders with the arguments.
+    **/
+    pub fn translate_with(&self, key: &str, args: &[(&str, &str)]) -> String {
+        self.catalog.translate_with(&self.locale, key, args)
+    }
+
+    /**
+    Translate the key of the catalog into the locale of the guild. Use it for public messages.
+    **/
+    pub fn translate_guild(&self, key: &str) -> String {
+        self.catalog.translate(self.guild_or_user_locale(), key)
+    }
+
+    /**
+    Translate the key into the locale of the guild and replace `{name}` placeholders with the arguments.
+    **/
+    pub fn translate_guild_with(&self, key: &str, args: &[(&str, &str)]) -> String {
+        self.catalog
+            .translate_with(self.guild_or_user_locale(), key, args)
+    }
+
+    pub fn get_option<T>(interaction: Box<ApplicationCommand>, name: &str) -> crate::Result<T>
     where
         T: FromCommandOptionValue,
     {
@@ -45,11 +112,12 @@ impl ChatInputCommandContext {
                 .options
                 .iter()
                 .find(|x| x.name == *name)
-                .unwrap()
+                .ok_or_else(|| crate::Error::MissingOption(name.to_string()))?
                 .clone()
                 .value,
+            interaction.data.resolved.as_ref(),
         )
-        .unwrap()
+        .map_err(|_| crate::Error::InvalidOption(name.to_string()))
     }
 
     pub fn message<F>(&self, message: F) -> InteractionResponse
@@ -80,4 +148,83 @@ impl ChatInputCommandContext {
         self.ctx.wait_until(future);
         builder.build(InteractionResponseType::DeferredChannelMessageWithSource)
     }
+
+    /**
+    Defer the response and run the work after responding.
+    The original response is edited with the message returned by the work.
+    If the work returns an error, it is edited with the failure message of [`DeferOptions`].
+
+    ```ignore
+    ctx.defer_with(|followup| async move {
+        let result = heavy_work().await?;
+        followup.edit(|msg| msg.content(result))
+    })
+    ```
+    **/
+    pub fn defer_with<W, Fut>(&self, work: W) -> InteractionResponse
+    where
+        W: FnOnce(Followup) -> Fut,
+        Fut: Future<Output = crate::Result<InteractionResponseBuilder>> + 'static,
+    {
+        self.defer_with_message(work, |msg| msg)
+    }
+
+    /**
+    Same as [`defer_with`](Self::defer_with), but the deferred response can be customized, like ephemeral.
+    **/
+    pub fn defer_with_message<W, Fut, M>(&self, work: W, message: M) -> InteractionResponse
+    where
+        W: FnOnce(Followup) -> Fut,
+        Fut: Future<Output = crate::Result<InteractionResponseBuilder>> + 'static,
+        M: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        let followup = self.followup_handle();
+        let future = work(followup.clone());
+        self.defer_and(
+            run_deferred(followup, self.defer_options.clone(), future),
+            message,
+        )
+    }
+
+    /**
+    A handle to the original response and the followups. It can be moved into deferred work.
+    **/
+    pub fn followup_handle(&self) -> Followup {
+        Followup::new(self.http.clone(), self.interaction.token.clone())
+            .with_interaction_id(self.interaction.id)
+    }
+
+    /**
+    Edit the original response. Use it to finish a deferred response.
+    **/
+    pub async fn edit_original<F>(&self, message: F) -> crate::Result<Message>
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        self.followup_handle().edit_original(message).await
+    }
+
+    /**
+    Send a followup message.
+    **/
+    pub async fn followup<F>(&self, message: F) -> crate::Result<Message>
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        self.followup_handle().create_followup(message).await
+    }
+
+    pub async fn delete_original(&self) -> crate::Result<()> {
+        self.followup_handle().delete_original().await
+    }
+
+    /**
+    Open the modal. You should create the modal with `Modal` derive macro.
+    **/
+    pub fn modal<M>(&self) -> InteractionResponse
+    where
+        M: Modal,
+    {
+        M::response()
+    }
 }
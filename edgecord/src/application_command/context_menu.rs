@@ -0,0 +1,87 @@
+use crate::application_command::ChatInputCommandContext;
+use std::ops::Deref;
+use twilight_model::application::interaction::application_command::InteractionMember;
+use twilight_model::channel::Message;
+use twilight_model::id::marker::{MessageMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_model::user::User;
+
+/**
+Context for User Command (right click on a user > Apps).
+**/
+pub struct UserCommandContext {
+    pub inner: ChatInputCommandContext,
+}
+
+impl UserCommandContext {
+    pub fn new(inner: ChatInputCommandContext) -> Self {
+        Self { inner }
+    }
+
+    /**
+    Returns the id of the target user.
+    **/
+    pub fn target_id(&self) -> Option<Id<UserMarker>> {
+        self.interaction.data.target_id.map(|id| id.cast())
+    }
+
+    /**
+    Returns the target user.
+    **/
+    pub fn target_user(&self) -> Option<&User> {
+        let id = self.target_id()?;
+        self.interaction.data.resolved.as_ref()?.users.get(&id)
+    }
+
+    /**
+    Returns the target member. It is `None` if the command is used outside of guilds.
+    **/
+    pub fn target_member(&self) -> Option<&InteractionMember> {
+        let id = self.target_id()?;
+        self.interaction.data.resolved.as_ref()?.members.get(&id)
+    }
+}
+
+impl Deref for UserCommandContext {
+    type Target = ChatInputCommandContext;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/**
+Context for Message Command (right click on a message > Apps).
+**/
+pub struct MessageCommandContext {
+    pub inner: ChatInputCommandContext,
+}
+
+impl MessageCommandContext {
+    pub fn new(inner: ChatInputCommandContext) -> Self {
+        Self { inner }
+    }
+
+    /**
+    Returns the id of the target message.
+    **/
+    pub fn target_id(&self) -> Option<Id<MessageMarker>> {
+        self.interaction.data.target_id.map(|id| id.cast())
+    }
+
+    /**
+    Returns the target message.
+    **/
+    pub fn target_message(&self) -> Option<&Message> {
+        let id = self.target_id()?;
+        self.interaction.data.resolved.as_ref()?.messages.get(&id)
+    }
+}
+
+impl Deref for MessageCommandContext {
+    type Target = ChatInputCommandContext;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
@@ -0,0 +1,211 @@
+use crate::builder::InteractionResponseBuilder;
+use crate::http::{Followup, INTERACTION_TOKEN_LIFETIME};
+use crate::log;
+use futures::future::Either;
+use std::future::Future;
+use std::rc::Rc;
+use std::time::Duration;
+
+const DEFAULT_FAILURE_MESSAGE: &str = "Failed to complete the command.";
+const DEFAULT_WARN_AFTER: Duration = Duration::from_secs(12 * 60);
+
+/**
+Options of deferred work started by [`defer_with`](super::ChatInputCommandContext::defer_with).
+**/
+#[derive(Debug, Clone)]
+pub struct DeferOptions {
+    /// The message to edit the original response with when the work returns an error.
+    pub failure_message: String,
+    /// Warn when the work is still running after this, because the token expires in 15 minutes.
+    pub warn_after: Duration,
+}
+
+impl Default for DeferOptions {
+    fn default() -> Self {
+        Self {
+            failure_message: DEFAULT_FAILURE_MESSAGE.to_string(),
+            warn_after: DEFAULT_WARN_AFTER,
+        }
+    }
+}
+
+/**
+How long the work took compared to the lifetime of the token.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenStatus {
+    Fresh,
+    Expiring,
+    Expired,
+}
+
+pub(crate) fn token_status(elapsed: Duration, warn_after: Duration) -> TokenStatus {
+    if elapsed >= INTERACTION_TOKEN_LIFETIME {
+        TokenStatus::Expired
+    } else if elapsed >= warn_after {
+        TokenStatus::Expiring
+    } else {
+        TokenStatus::Fresh
+    }
+}
+
+/**
+Wait for the work and edit the original response with the result.
+It warns when `warn_after` elapses while the work is running,
+and doesn't edit the response if the token has expired when the work finishes.
+**/
+pub(crate) async fn run_deferred<F>(followup: Followup, options: Rc<DeferOptions>, work: F)
+where
+    F: Future<Output = crate::Result<InteractionResponseBuilder>>,
+{
+    futures::pin_mut!(work);
+    let delay = followup.sleep(options.warn_after.saturating_sub(followup.elapsed()));
+    futures::pin_mut!(delay);
+    let result = match futures::future::select(work, delay).await {
+        Either::Left((result, _)) => result,
+        Either::Right((timer, work)) => {
+            // without a timer, the work is just awaited
+            if timer.is_ok() {
+                log::warn(&format!(
+                    "deferred work is still running after {}s, the interaction token expires in 15 minutes",
+                    followup.elapsed().as_secs()
+                ));
+            }
+            work.await
+        }
+    };
+
+    let elapsed = followup.elapsed();
+    if token_status(elapsed, options.warn_after) == TokenStatus::Expired {
+        let outcome = match &result {
+            Ok(_) => "succeeded".to_string(),
+            Err(err) => format!("failed ({})", err),
+        };
+        log::warn(&format!(
+            "deferred work {} after {}s, but the interaction token has expired, so the original response is not edited",
+            outcome,
+            elapsed.as_secs()
+        ));
+        return;
+    }
+
+    let edited = match result {
+        Ok(builder) => {
+            followup
+                .edit_original(move |msg| {
+                    *msg = builder;
+                    msg
+                })
+                .await
+        }
+        Err(err) => {
+            log::error(&err.to_string());
+            followup
+                .edit_original(|msg| msg.content(&options.failure_message))
+                .await
+        }
+    };
+    if let Err(err) = edited {
+        log::error(&format!("failed to edit the original response: {}", err));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::transport::MockTransport;
+    use crate::http::{now_millis, HttpClient, Transport, TransportRequest, TransportResponse};
+    use async_trait::async_trait;
+    use twilight_model::id::Id;
+    use worker::Method;
+
+    #[test]
+    fn test_token_status() {
+        let warn_after = Duration::from_secs(12 * 60);
+        assert_eq!(
+            token_status(Duration::from_secs(60), warn_after),
+            TokenStatus::Fresh
+        );
+        assert_eq!(
+            token_status(Duration::from_secs(13 * 60), warn_after),
+            TokenStatus::Expiring
+        );
+        assert_eq!(
+            token_status(Duration::from_secs(15 * 60), warn_after),
+            TokenStatus::Expired
+        );
+    }
+
+    /// A transport whose timer fires at once.
+    #[derive(Default)]
+    struct InstantTimer {
+        mock: MockTransport,
+    }
+
+    #[async_trait(?Send)]
+    impl Transport for InstantTimer {
+        async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+            self.mock.send(request).await
+        }
+
+        async fn sleep(&self, _duration: Duration) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// The work that yields once, so that the timer fires before it finishes.
+    async fn slow_work() -> crate::Result<InteractionResponseBuilder> {
+        let mut yielded = false;
+        futures::future::poll_fn(|cx| {
+            if yielded {
+                std::task::Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+        })
+        .await;
+        let mut builder = InteractionResponseBuilder::default();
+        builder.content("done");
+        Ok(builder)
+    }
+
+    fn followup(created_ago: Duration) -> (Rc<InstantTimer>, Followup) {
+        let transport = Rc::new(InstantTimer::default());
+        let http = HttpClient::new("token", Id::new(1)).with_transport(transport.clone());
+        let created_at = now_millis() - created_ago.as_millis() as u64;
+        let id = Id::new((created_at - 1_420_070_400_000) << 22);
+        (
+            transport,
+            Followup::new(http, "interaction-token".to_string()).with_interaction_id(id),
+        )
+    }
+
+    #[test]
+    fn test_run_deferred() {
+        let (transport, followup) = followup(Duration::from_secs(60));
+        futures::executor::block_on(run_deferred(
+            followup,
+            Rc::new(DeferOptions::default()),
+            slow_work(),
+        ));
+        let requests = transport.mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::Patch);
+        assert!(requests[0]
+            .url
+            .ends_with("/webhooks/1/interaction-token/messages/@original"));
+    }
+
+    #[test]
+    fn test_run_deferred_expired() {
+        let (transport, followup) = followup(Duration::from_secs(16 * 60));
+        futures::executor::block_on(run_deferred(
+            followup,
+            Rc::new(DeferOptions::default()),
+            slow_work(),
+        ));
+        assert!(transport.mock.requests().is_empty());
+    }
+}
@@ -1,10 +1,71 @@
 use crate::application_command::{Command, I18nMap, SubCommand};
+use crate::cooldown::Cooldown;
 use serde::ser::SerializeStruct;
 use serde::{Serialize, Serializer};
+use std::collections::HashSet;
 use twilight_model::application::command::CommandOptionType;
 use twilight_model::application::interaction::application_command::{
     CommandDataOption, CommandOptionValue,
 };
+use twilight_model::application::interaction::application_command_autocomplete::{
+    ApplicationCommandAutocompleteDataOption, ApplicationCommandAutocompleteDataOptionType,
+};
+
+const SUBCOMMAND_LIMIT: usize = 25;
+
+/**
+Trait for interaction options that may contain subcommands.
+It is used to find the command from commands and groups.
+**/
+pub trait CommandOptionTree: Clone {
+    fn name(&self) -> &str;
+    /// Returns the options of the subcommand if this option is a subcommand.
+    fn subcommand(&self) -> Option<Vec<Self>>;
+    /// Returns the subcommands if this option is a subcommand group.
+    fn subcommand_group(&self) -> Option<Vec<Self>>;
+}
+
+impl CommandOptionTree for CommandDataOption {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn subcommand(&self) -> Option<Vec<Self>> {
+        match &self.value {
+            CommandOptionValue::SubCommand(options) => Some(options.clone()),
+            _ => None,
+        }
+    }
+
+    fn subcommand_group(&self) -> Option<Vec<Self>> {
+        match &self.value {
+            CommandOptionValue::SubCommandGroup(options) => Some(options.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl CommandOptionTree for ApplicationCommandAutocompleteDataOption {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn subcommand(&self) -> Option<Vec<Self>> {
+        match self.kind {
+            ApplicationCommandAutocompleteDataOptionType::SubCommand => Some(self.options.clone()),
+            _ => None,
+        }
+    }
+
+    fn subcommand_group(&self) -> Option<Vec<Self>> {
+        match self.kind {
+            ApplicationCommandAutocompleteDataOptionType::SubCommandGroup => {
+                Some(self.options.clone())
+            }
+            _ => None,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct CommandGroup {
@@ -13,6 +74,9 @@ pub struct CommandGroup {
     pub description: String,
     pub i18n_descriptions: I18nMap,
     pub default_permissions: Option<u64>,
+    pub dm_permission: Option<bool>,
+    pub nsfw: bool,
+    pub cooldown: Option<Cooldown>,
 
     pub commands: Vec<SubCommand>,
 }
@@ -22,7 +86,7 @@ impl Serialize for CommandGroup {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("CommandGroup", 6)?;
+        let mut state = serializer.serialize_struct("CommandGroup", 8)?;
         state.serialize_field("name", self.name.as_str())?;
         state.serialize_field("description", self.description.as_str())?;
 
@@ -39,11 +103,23 @@ impl Serialize for CommandGroup {
         }
 
         if let Some(permissions) = self.default_permissions {
-            state.serialize_field("default_member_permissions", &permissions)?;
+            state.serialize_field("default_member_permissions", &permissions.to_string())?;
         } else {
             state.skip_field("default_member_permissions")?;
         }
 
+        if let Some(dm_permission) = self.dm_permission {
+            state.serialize_field("dm_permission", &dm_permission)?;
+        } else {
+            state.skip_field("dm_permission")?;
+        }
+
+        if self.nsfw {
+            state.serialize_field("nsfw", &true)?;
+        } else {
+            state.skip_field("nsfw")?;
+        }
+
         state.serialize_field("options", &self.commands)?;
 
         state.end()
@@ -51,43 +127,84 @@ impl Serialize for CommandGroup {
 }
 
 impl CommandGroup {
-    pub fn get_command(
-        &self,
-        option: CommandDataOption,
-    ) -> Option<(Command, Vec<CommandDataOption>)> {
-        match option.value {
-            CommandOptionValue::SubCommand(options) => {
-                if let Some(SubCommand::Command(command)) = self
-                    .commands
-                    .iter()
-                    .filter(|x| !x.is_group())
-                    .find(|x| x.get_name() == option.name)
-                    .cloned()
-                {
-                    Some((command, options))
-                } else {
-                    None
+    /**
+    Validate the structure of the group.
+    A group can have up to 25 subcommands and groups, and a group in a group can't have groups.
+    The structure is made at runtime, so the macros can't check it. The options of the subcommands are validated too.
+    **/
+    pub fn validate(&self) -> crate::Result<()> {
+        self.validate_depth(0)
+    }
+
+    fn validate_depth(&self, depth: usize) -> crate::Result<()> {
+        if self.commands.is_empty() || self.commands.len() > SUBCOMMAND_LIMIT {
+            return Err(crate::Error::InvalidCommand(format!(
+                "group `{}` must have 1 to {} subcommands ({})",
+                self.name,
+                SUBCOMMAND_LIMIT,
+                self.commands.len()
+            )));
+        }
+        let mut names = HashSet::new();
+        for command in &self.commands {
+            if !names.insert(command.get_name()) {
+                return Err(crate::Error::InvalidCommand(format!(
+                    "group `{}` has duplicated subcommand `{}`",
+                    self.name,
+                    command.get_name()
+                )));
+            }
+            match command {
+                SubCommand::Group(group) => {
+                    if depth > 0 {
+                        return Err(crate::Error::InvalidCommand(format!(
+                            "subcommand group `{}` can't have subcommand group `{}`",
+                            self.name, group.name
+                        )));
+                    }
+                    group.validate_depth(depth + 1)?;
                 }
+                SubCommand::Command(command) => command.validate()?,
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_command<O>(&self, option: O) -> Option<(Command, Vec<O>)>
+    where
+        O: CommandOptionTree,
+    {
+        if let Some(options) = option.subcommand() {
+            if let Some(SubCommand::Command(command)) = self
+                .commands
+                .iter()
+                .filter(|x| !x.is_group())
+                .find(|x| x.get_name() == option.name())
+                .cloned()
+            {
+                Some((command, options))
+            } else {
+                None
             }
-            CommandOptionValue::SubCommandGroup(subcommands) => {
-                if let Some(SubCommand::Group(group)) = self
-                    .commands
+        } else if let Some(subcommands) = option.subcommand_group() {
+            if let Some(SubCommand::Group(group)) = self
+                .commands
+                .iter()
+                .filter(|x| x.is_group())
+                .find(|x| x.get_name() == option.name())
+                .cloned()
+            {
+                subcommands
                     .iter()
-                    .filter(|x| x.is_group())
-                    .find(|x| x.get_name() == option.name)
+                    .filter_map(|x| group.get_command(x.clone()))
+                    .collect::<Vec<_>>()
+                    .first()
                     .cloned()
-                {
-                    subcommands
-                        .iter()
-                        .filter_map(|x| group.get_command(x.clone()))
-                        .collect::<Vec<_>>()
-                        .first()
-                        .cloned()
-                } else {
-                    None
-                }
+            } else {
+                None
             }
-            _ => None,
+        } else {
+            None
         }
     }
 }
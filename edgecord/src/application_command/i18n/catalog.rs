@@ -0,0 +1,143 @@
+use crate::application_command::i18n::Locales;
+use edgelord::i18n::lookup;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/**
+Translations of response texts. Create it with `catalog!` macro.
+
+The texts can have placeholders like `{user}`, and they are replaced by [`Catalog::translate_with`].
+If a locale doesn't have the key, the fallback chain of the locale and then the fallback locale are tried.
+**/
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    fallback: Option<Locales>,
+    chains: HashMap<Locales, Vec<Locales>>,
+    messages: HashMap<Locales, Value>,
+}
+
+impl Catalog {
+    pub fn new(fallback: Locales) -> Self {
+        Self {
+            fallback: Some(fallback),
+            ..Self::default()
+        }
+    }
+
+    /**
+    Set the locales to try before the fallback locale, like `pt-BR -> es-ES -> en-US`.
+
+    ```ignore
+    catalog.fallback_chain(Locales::PtBR, vec![Locales::EsES]);
+    ```
+    **/
+    pub fn fallback_chain(&mut self, locale: Locales, chain: Vec<Locales>) -> &mut Self {
+        self.chains.insert(locale, chain);
+        self
+    }
+
+    /**
+    The locales to try in order for the locale.
+    **/
+    pub fn candidates(&self, locale: &Locales) -> Vec<Locales> {
+        let mut candidates: Vec<Locales> = vec![];
+        let chain = self.chains.get(locale).cloned().unwrap_or_default();
+        for x in std::iter::once(locale.clone())
+            .chain(chain)
+            .chain(self.fallback.clone())
+        {
+            if !candidates.contains(&x) {
+                candidates.push(x);
+            }
+        }
+        candidates
+    }
+
+    /**
+    Negotiate the locale: the first candidate that the catalog has translations for.
+    **/
+    pub fn negotiate(&self, locale: &Locales) -> Option<Locales> {
+        self.candidates(locale)
+            .into_iter()
+            .find(|x| self.messages.contains_key(x))
+    }
+
+    /**
+    Add the translations of the locale from JSON.
+    Panics if the JSON is invalid. `catalog!` checks it at compile time.
+    **/
+    pub fn add_json(&mut self, locale: Locales, json: &str) -> &mut Self {
+        self.messages.insert(
+            locale,
+            serde_json::from_str(json).expect("invalid catalog json"),
+        );
+        self
+    }
+
+    /**
+    Get the text of the key. If the locale doesn't have the key, the fallback chain is used.
+    **/
+    pub fn get(&self, locale: &Locales, key: &str) -> Option<&str> {
+        self.candidates(locale)
+            .iter()
+            .find_map(|x| self.messages.get(x).and_then(|x| lookup(x, key)))
+    }
+
+    /**
+    Translate the key. If no locale has the key, the key itself is returned.
+    **/
+    pub fn translate(&self, locale: &Locales, key: &str) -> String {
+        self.get(locale, key).unwrap_or(key).to_string()
+    }
+
+    /**
+    Translate the key and replace `{name}` placeholders with the arguments.
+    **/
+    pub fn translate_with(&self, locale: &Locales, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(self.translate(locale, key), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        let mut catalog = Catalog::new(Locales::EnUS);
+        catalog
+            .add_json(
+                Locales::EnUS,
+                r#"{"responses": {"banned": "banned {user}", "bye": "bye"}}"#,
+            )
+            .add_json(
+                Locales::Ja,
+                r#"{"responses.banned": "{user}をバンしました"}"#,
+            );
+        assert_eq!(
+            catalog.translate_with(&Locales::Ja, "responses.banned", &[("user", "sizumita")]),
+            "sizumitaをバンしました"
+        );
+        assert_eq!(catalog.translate(&Locales::Ja, "responses.bye"), "bye");
+        assert_eq!(catalog.translate(&Locales::Ja, "unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let mut catalog = Catalog::new(Locales::EnUS);
+        catalog
+            .add_json(Locales::EnUS, r#"{"hello": "hello", "bye": "bye"}"#)
+            .add_json(Locales::EsES, r#"{"hello": "hola"}"#)
+            .fallback_chain(Locales::PtBR, vec![Locales::EsES]);
+        assert_eq!(catalog.translate(&Locales::PtBR, "hello"), "hola");
+        assert_eq!(catalog.translate(&Locales::PtBR, "bye"), "bye");
+        assert_eq!(catalog.negotiate(&Locales::PtBR), Some(Locales::EsES));
+        assert_eq!(
+            catalog.negotiate(&Locales::Other("id".to_string())),
+            Some(Locales::EnUS)
+        );
+    }
+}
@@ -1,50 +1,74 @@
-use serde::{Deserialize, Serialize};
+mod catalog;
 
-/**
-Discord i18n Locales.
+pub use catalog::Catalog;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
 
-https://discord.com/developers/docs/reference#locales
-**/
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "lowercase")]
-pub enum Locales {
-    Da,
-    De,
-    #[serde(rename = "en-GB")]
-    EnGB,
-    #[serde(rename = "en-US")]
-    EnUS,
-    #[serde(rename = "es-ES")]
-    EsES,
-    Fr,
-    Hr,
-    It,
-    Lt,
-    Hu,
-    Nl,
-    No,
-    Pl,
-    #[serde(rename = "pt-BR")]
-    PtBR,
-    Ro,
-    Fi,
-    #[serde(rename = "sv-SE")]
-    SvSE,
-    Vi,
-    Tr,
-    Cs,
-    El,
-    Bg,
-    Ru,
-    Uk,
-    Hi,
-    Th,
-    #[serde(rename = "zh-CN")]
-    ZhCN,
-    Ja,
-    #[serde(rename = "zh-TW")]
-    ZhTW,
-    Ko,
+macro_rules! locales {
+    ($($code:literal => $variant:ident,)*) => {
+        /**
+        Discord i18n Locales.
+
+        The locales that Discord adds later are kept as `Other`, so that the locale string is never lost.
+
+        https://discord.com/developers/docs/reference#locales
+        **/
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum Locales {
+            $($variant,)*
+            Other(String),
+        }
+
+        impl Locales {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Locales::$variant => $code,)*
+                    Locales::Other(locale) => locale,
+                }
+            }
+        }
+
+        impl From<&str> for Locales {
+            fn from(locale: &str) -> Self {
+                match locale {
+                    $($code => Locales::$variant,)*
+                    other => Locales::Other(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+edgelord::with_locales!(locales);
+
+impl From<Locales> for String {
+    fn from(locale: Locales) -> Self {
+        locale.as_str().to_string()
+    }
+}
+
+impl Display for Locales {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for Locales {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Locales {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Locales::from(String::deserialize(deserializer)?.as_str()))
+    }
 }
 
 #[cfg(test)]
@@ -60,10 +84,12 @@ mod tests {
     fn test_locale_2() {
         assert_eq!(serde_json::to_string(&Locales::Ja).unwrap(), "\"ja\"")
     }
-}
 
-impl From<Locales> for String {
-    fn from(locale: Locales) -> Self {
-        serde_json::to_string(&locale).unwrap()
+    #[test]
+    fn test_locale_other() {
+        let locale = serde_json::from_str::<Locales>("\"id\"").unwrap();
+        assert_eq!(locale, Locales::Other("id".to_string()));
+        assert_eq!(serde_json::to_string(&locale).unwrap(), "\"id\"");
+        assert_eq!(Locales::from("pt-BR"), Locales::PtBR);
     }
 }
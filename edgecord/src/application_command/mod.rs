@@ -1,21 +1,30 @@
+pub mod autocomplete;
 pub mod choice;
 pub mod context;
+pub mod context_menu;
+pub mod deferred;
 pub mod group;
 pub mod i18n;
 pub mod option;
 
 use futures::future::LocalBoxFuture;
 use serde::{Serialize, Serializer};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use twilight_model::application::command::CommandType;
 use twilight_model::application::interaction::application_command::CommandDataOption;
+use twilight_model::application::interaction::application_command_autocomplete::ApplicationCommandAutocompleteDataOption;
 use twilight_model::application::interaction::ApplicationCommand;
+use twilight_model::http::interaction::{InteractionResponseData, InteractionResponseType};
 
+use crate::cooldown::Cooldown;
 use crate::InteractionResponse;
+pub use autocomplete::*;
 pub use choice::*;
 pub use context::*;
+pub use context_menu::*;
+pub use deferred::DeferOptions;
 pub use group::*;
 pub use option::*;
 
@@ -26,7 +35,15 @@ type AsyncCommandFn = Rc<
             ChatInputCommandContext,
             Box<ApplicationCommand>,
             Vec<CommandDataOption>,
-        ) -> LocalBoxFuture<'static, InteractionResponse>,
+        ) -> LocalBoxFuture<'static, crate::Result<InteractionResponse>>,
+>;
+type AsyncAutocompleteFn = Rc<
+    dyn 'static
+        + Fn(
+            AutocompleteContext,
+            ApplicationCommandAutocompleteDataOption,
+            Vec<ApplicationCommandAutocompleteDataOption>,
+        ) -> LocalBoxFuture<'static, Vec<Choice>>,
 >;
 
 fn serialize_permissions<S: serde::Serializer>(
@@ -34,7 +51,7 @@ fn serialize_permissions<S: serde::Serializer>(
     s: S,
 ) -> Result<S::Ok, S::Error> {
     if let Some(value) = value {
-        s.serialize_u64(*value)
+        s.serialize_str(&value.to_string())
     } else {
         s.serialize_none()
     }
@@ -58,14 +75,25 @@ pub struct Command {
     pub i18n_descriptions: I18nMap,
     #[serde(
         skip_serializing_if = "Option::is_none",
-        serialize_with = "serialize_permissions"
+        serialize_with = "serialize_permissions",
+        rename = "default_member_permissions"
     )]
     pub default_permissions: Option<u64>,
+    /// Whether the command is available in DMs. Only for global commands.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dm_permission: Option<bool>,
+    /// Whether the command is age-restricted.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub nsfw: bool,
 
     pub options: Vec<CommandOption>,
 
+    #[serde(skip)]
+    pub cooldown: Option<Cooldown>,
     #[serde(skip)]
     pub action: AsyncCommandFn,
+    #[serde(skip)]
+    pub autocomplete: Option<AsyncAutocompleteFn>,
 }
 
 impl Debug for Command {
@@ -80,28 +108,95 @@ impl Debug for Command {
             "    default_permissions: {:?},",
             self.default_permissions
         )?;
+        writeln!(f, "    dm_permission: {:?},", self.dm_permission)?;
+        writeln!(f, "    nsfw: {:?},", self.nsfw)?;
         writeln!(f, "    options: {:?},", self.options)?;
+        writeln!(f, "    cooldown: {:?},", self.cooldown)?;
         write!(f, "}}")
     }
 }
 
-impl ToString for Command {
+impl std::fmt::Display for Command {
     /**
-    Returns JSON serialized string.
+    Writes JSON serialized string.
     **/
-    fn to_string(&self) -> String {
-        serde_json::to_string(&self).unwrap()
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(&self).unwrap())
     }
 }
 
+const OPTION_LIMIT: usize = 25;
+
 impl Command {
+    /**
+    Validate the options: the count, the duplicated names and the order of required options.
+    The macros check the parameters, but the fields of `#[options]` structs are expanded at runtime.
+    **/
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.options.len() > OPTION_LIMIT {
+            return Err(crate::Error::InvalidCommand(format!(
+                "command `{}` can't have more than {} options ({})",
+                self.name,
+                OPTION_LIMIT,
+                self.options.len()
+            )));
+        }
+        let mut names = HashSet::new();
+        let mut optional = false;
+        for option in &self.options {
+            if !names.insert(option.name.as_str()) {
+                return Err(crate::Error::InvalidCommand(format!(
+                    "command `{}` has duplicated option `{}`",
+                    self.name, option.name
+                )));
+            }
+            if option.required && optional {
+                return Err(crate::Error::InvalidCommand(format!(
+                    "required option `{}` of command `{}` must be placed before optional options",
+                    option.name, self.name
+                )));
+            }
+            optional |= !option.required;
+        }
+        Ok(())
+    }
+
     pub async fn invoke(
         &self,
         ctx: ChatInputCommandContext,
         interaction: Box<ApplicationCommand>,
         options: Vec<CommandDataOption>,
-    ) -> worker::Result<worker::Response> {
-        worker::Response::from_json(&(self.action)(ctx, interaction, options).await)
+    ) -> crate::Result<InteractionResponse> {
+        (self.action)(ctx, interaction, options).await
+    }
+
+    /**
+    Call the autocomplete function of the focused option and response up to 25 choices.
+    **/
+    pub async fn invoke_autocomplete(
+        &self,
+        ctx: AutocompleteContext,
+        options: Vec<ApplicationCommandAutocompleteDataOption>,
+    ) -> InteractionResponse {
+        let choices = match (&self.autocomplete, get_focused_option(&options)) {
+            (Some(autocomplete), Some(focused)) => autocomplete(ctx, focused, options).await,
+            _ => vec![],
+        };
+        InteractionResponse {
+            kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+            data: Some(InteractionResponseData {
+                allowed_mentions: None,
+                attachments: None,
+                choices: Some(choices.into_iter().take(25).map(|x| x.into()).collect()),
+                components: None,
+                content: None,
+                custom_id: None,
+                embeds: None,
+                flags: None,
+                title: None,
+                tts: None,
+            }),
+        }
     }
 }
 
@@ -142,3 +237,57 @@ impl SubCommand {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{command, CommandOptions};
+
+    #[derive(CommandOptions)]
+    struct Target {
+        #[option(description = "the reason")]
+        reason: Option<String>,
+    }
+
+    #[command(description = "ban the user")]
+    async fn ban(
+        ctx: ChatInputCommandContext,
+        #[options] target: Target,
+        #[option(description = "the days")] days: i64,
+    ) -> InteractionResponse {
+        ctx.message(|msg| msg.content(format!("{:?} {}", target.reason, days)))
+    }
+
+    #[command(description = "kick the user")]
+    async fn kick(
+        ctx: ChatInputCommandContext,
+        #[option(description = "the note")] note: String,
+        #[options] target: Target,
+    ) -> InteractionResponse {
+        ctx.message(|msg| msg.content(format!("{} {:?}", note, target.reason)))
+    }
+
+    #[test]
+    fn test_validate_options() {
+        assert!(kick().validate().is_ok());
+        assert!(ban()
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("before optional"));
+
+        let mut command = kick();
+        command.options.push(command.options[0].clone());
+        assert!(command
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("duplicated"));
+        command.options = vec![command.options[1].clone(); 26];
+        assert!(command
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("more than 25"));
+    }
+}
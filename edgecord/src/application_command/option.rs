@@ -1,13 +1,22 @@
-use crate::application_command::{Choice, Command, I18nMap};
+use crate::application_command::{AutocompleteContext, Choice, Command, I18nMap};
 use crate::Error;
+use futures::future::LocalBoxFuture;
 use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::hash::Hash;
 use twilight_model::application::command::CommandOptionType;
-use twilight_model::application::interaction::application_command::CommandOptionValue;
-use twilight_model::channel::ChannelType;
+use twilight_model::application::interaction::application_command::{
+    CommandDataOption, CommandInteractionDataResolved, CommandOptionValue, InteractionChannel,
+    InteractionMember,
+};
+use twilight_model::application::interaction::application_command_autocomplete::ApplicationCommandAutocompleteDataOption;
+use twilight_model::channel::{Attachment, ChannelType};
+use twilight_model::guild::Role;
 use twilight_model::id::marker::{
     AttachmentMarker, ChannelMarker, GenericMarker, RoleMarker, UserMarker,
 };
 use twilight_model::id::Id;
+use twilight_model::user::User;
 
 #[derive(Debug, Clone)]
 pub enum RangeValue {
@@ -60,7 +69,13 @@ pub struct CommandOption {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_value: Option<RangeValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub channel_types: Option<Vec<ChannelType>>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub autocomplete: bool,
 }
 
 #[derive(Clone, Serialize)]
@@ -94,9 +109,14 @@ impl From<Command> for CommandAsSubCommand {
 
 /**
 Trait for command option. If you implemented it, you can use it for command option.
+
+`resolved` is the resolved data of the interaction. It contains the users, members, roles, channels and attachments of the options.
 **/
 pub trait FromCommandOptionValue {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized;
     fn get_option_type() -> CommandOptionType;
@@ -106,7 +126,10 @@ pub trait FromCommandOptionValue {
 }
 
 impl FromCommandOptionValue for Id<ChannelMarker> {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -122,7 +145,10 @@ impl FromCommandOptionValue for Id<ChannelMarker> {
 }
 
 impl FromCommandOptionValue for Id<RoleMarker> {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -138,7 +164,10 @@ impl FromCommandOptionValue for Id<RoleMarker> {
 }
 
 impl FromCommandOptionValue for Id<UserMarker> {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -154,7 +183,10 @@ impl FromCommandOptionValue for Id<UserMarker> {
 }
 
 impl FromCommandOptionValue for Id<GenericMarker> {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -170,7 +202,10 @@ impl FromCommandOptionValue for Id<GenericMarker> {
 }
 
 impl FromCommandOptionValue for Id<AttachmentMarker> {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -186,7 +221,10 @@ impl FromCommandOptionValue for Id<AttachmentMarker> {
 }
 
 impl FromCommandOptionValue for String {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -202,7 +240,10 @@ impl FromCommandOptionValue for String {
 }
 
 impl FromCommandOptionValue for i64 {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -218,7 +259,10 @@ impl FromCommandOptionValue for i64 {
 }
 
 impl FromCommandOptionValue for bool {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -234,7 +278,10 @@ impl FromCommandOptionValue for bool {
 }
 
 impl FromCommandOptionValue for f64 {
-    fn from_option(value: CommandOptionValue) -> Result<Self, crate::Error>
+    fn from_option(
+        value: CommandOptionValue,
+        _resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
     where
         Self: Sized,
     {
@@ -249,16 +296,181 @@ impl FromCommandOptionValue for f64 {
     }
 }
 
+/**
+Get the resolved object of the id.
+**/
+fn get_resolved<K, V>(map: Option<&HashMap<K, V>>, id: &K) -> Result<V, crate::Error>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    map.and_then(|map| map.get(id))
+        .cloned()
+        .ok_or(Error::MissingResolved)
+}
+
+impl FromCommandOptionValue for User {
+    fn from_option(
+        value: CommandOptionValue,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            CommandOptionValue::User(id) => get_resolved(resolved.map(|x| &x.users), &id),
+            _ => Err(Error::WrongOptionType),
+        }
+    }
+
+    fn get_option_type() -> CommandOptionType {
+        CommandOptionType::User
+    }
+}
+
+/**
+The user and the member. The member is None if the command is used in DM or the user is not in the guild.
+**/
+impl FromCommandOptionValue for (User, Option<InteractionMember>) {
+    fn from_option(
+        value: CommandOptionValue,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            CommandOptionValue::User(id) => Ok((
+                get_resolved(resolved.map(|x| &x.users), &id)?,
+                get_resolved(resolved.map(|x| &x.members), &id).ok(),
+            )),
+            _ => Err(Error::WrongOptionType),
+        }
+    }
+
+    fn get_option_type() -> CommandOptionType {
+        CommandOptionType::User
+    }
+}
+
+impl FromCommandOptionValue for Role {
+    fn from_option(
+        value: CommandOptionValue,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            CommandOptionValue::Role(id) => get_resolved(resolved.map(|x| &x.roles), &id),
+            _ => Err(Error::WrongOptionType),
+        }
+    }
+
+    fn get_option_type() -> CommandOptionType {
+        CommandOptionType::Role
+    }
+}
+
+impl FromCommandOptionValue for InteractionChannel {
+    fn from_option(
+        value: CommandOptionValue,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            CommandOptionValue::Channel(id) => get_resolved(resolved.map(|x| &x.channels), &id),
+            _ => Err(Error::WrongOptionType),
+        }
+    }
+
+    fn get_option_type() -> CommandOptionType {
+        CommandOptionType::Channel
+    }
+}
+
+impl FromCommandOptionValue for Attachment {
+    fn from_option(
+        value: CommandOptionValue,
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> Result<Self, crate::Error>
+    where
+        Self: Sized,
+    {
+        match value {
+            CommandOptionValue::Attachment(id) => {
+                get_resolved(resolved.map(|x| &x.attachments), &id)
+            }
+            _ => Err(Error::WrongOptionType),
+        }
+    }
+
+    fn get_option_type() -> CommandOptionType {
+        CommandOptionType::Attachment
+    }
+}
+
+/**
+Trait for a set of command options. Derive it by `#[derive(CommandOptions)]`.
+The struct is used as the schema of the options and as the parsed arguments.
+**/
+pub trait CommandOptions: Sized {
+    fn options() -> Vec<CommandOption>;
+    fn from_options(
+        options: &[CommandDataOption],
+        resolved: Option<&CommandInteractionDataResolved>,
+    ) -> crate::Result<Self>;
+    /**
+    Whether the option has autocomplete function.
+    **/
+    fn has_autocomplete(_name: &str) -> bool {
+        false
+    }
+    fn autocomplete(
+        _ctx: AutocompleteContext,
+        _focused: ApplicationCommandAutocompleteDataOption,
+        _options: Vec<ApplicationCommandAutocompleteDataOption>,
+    ) -> LocalBoxFuture<'static, Vec<Choice>> {
+        Box::pin(async { vec![] })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::FromCommandOptionValue;
-    use twilight_model::application::interaction::application_command::CommandOptionValue;
+    use twilight_model::application::interaction::application_command::{
+        CommandInteractionDataResolved, CommandOptionValue, InteractionMember,
+    };
+    use twilight_model::id::Id;
+    use twilight_model::user::User;
 
     #[test]
     fn test_string() {
         assert_eq!(
-            &String::from_option(CommandOptionValue::String("avc".to_string())).unwrap(),
+            &String::from_option(CommandOptionValue::String("avc".to_string()), None).unwrap(),
             "avc"
         )
     }
+
+    #[test]
+    fn test_resolved_user() {
+        let resolved: CommandInteractionDataResolved = serde_json::from_value(serde_json::json!({
+            "users": {
+                "100": {"id": "100", "username": "edgecord", "discriminator": "0001", "avatar": null}
+            }
+        }))
+        .unwrap();
+        let (user, member) = <(User, Option<InteractionMember>)>::from_option(
+            CommandOptionValue::User(Id::new(100)),
+            Some(&resolved),
+        )
+        .unwrap();
+        assert_eq!(user.name, "edgecord");
+        assert!(member.is_none());
+        assert!(
+            User::from_option(CommandOptionValue::User(Id::new(200)), Some(&resolved)).is_err()
+        );
+    }
 }
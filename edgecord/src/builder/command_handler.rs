@@ -1,19 +1,74 @@
-use crate::application_command::{Command, CommandGroup};
-use crate::handler::InteractionHandler;
+use crate::application_command::i18n::Catalog;
+use crate::application_command::{Command, CommandGroup, DeferOptions};
+use crate::cooldown::{CooldownStore, MemoryCooldownStore};
+use crate::handler::{default_error_handler, ErrorHandler, InteractionHandler, IntoResponseResult};
+use crate::http::{DefaultRateLimitBucket, HttpConfig, RateLimitBucket, Transport};
+use crate::message_component::Component;
+use crate::middleware::Middleware;
+use crate::modal::{Modal, ModalContext, ModalSubmit};
+use crate::verify::ReplayStore;
+use crate::{Error, InteractionResponse};
 use ed25519_dalek::PublicKey;
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
 use std::str::FromStr;
+use std::time::Duration;
+use twilight_model::application::interaction::Interaction;
 use twilight_model::id::Id;
 
+const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_COOLDOWN_MESSAGE: &str = "You are on cooldown. Try again in {seconds}s.";
+
 /**
 A builder for [`InteractionHandler`].
  **/
-#[derive(Default)]
 pub struct CommandHandlerBuilder {
     commands: Vec<Command>,
     groups: Vec<CommandGroup>,
+    components: Vec<Component>,
+    modals: Vec<ModalSubmit>,
     public_key: Option<String>,
     token: Option<String>,
     application_id: Option<String>,
+    error_handler: ErrorHandler,
+    rate_limit_bucket: Rc<dyn RateLimitBucket>,
+    max_clock_skew: Option<Duration>,
+    replay_store: Option<Rc<dyn ReplayStore>>,
+    catalog: Rc<Catalog>,
+    defer_options: DeferOptions,
+    middlewares: Vec<Rc<dyn Middleware>>,
+    command_middlewares: HashMap<String, Vec<Rc<dyn Middleware>>>,
+    cooldown_store: Rc<dyn CooldownStore>,
+    cooldown_message: String,
+    transport: Option<Rc<dyn Transport>>,
+    http_config: HttpConfig,
+}
+
+impl Default for CommandHandlerBuilder {
+    fn default() -> Self {
+        Self {
+            commands: vec![],
+            groups: vec![],
+            components: vec![],
+            modals: vec![],
+            public_key: None,
+            token: None,
+            application_id: None,
+            error_handler: Rc::new(default_error_handler),
+            rate_limit_bucket: Rc::new(DefaultRateLimitBucket::new()),
+            max_clock_skew: Some(DEFAULT_MAX_CLOCK_SKEW),
+            replay_store: None,
+            catalog: Rc::new(Catalog::default()),
+            defer_options: DeferOptions::default(),
+            middlewares: vec![],
+            command_middlewares: HashMap::new(),
+            cooldown_store: Rc::new(MemoryCooldownStore::new()),
+            cooldown_message: DEFAULT_COOLDOWN_MESSAGE.to_string(),
+            transport: None,
+            http_config: HttpConfig::default(),
+        }
+    }
 }
 
 impl CommandHandlerBuilder {
@@ -39,6 +94,238 @@ impl CommandHandlerBuilder {
         self
     }
 
+    /**
+    Register message component handler for [`InteractionHandler`].
+    You should create [`Component`] with `component` macro.
+    **/
+    pub fn component(&mut self, component: Component) -> &mut Self {
+        self.components.push(component);
+        self
+    }
+
+    /**
+    Register modal submit handler for [`InteractionHandler`].
+    The submit is routed by the custom id of the modal `M`, and parsed into `M`.
+
+    # Example
+
+    ```ignore
+    async fn feedback_submitted(ctx: ModalContext, form: Feedback) -> InteractionResponse {
+        ctx.message(|msg| msg.content(form.comment))
+    }
+
+    InteractionHandler::builder().modal(feedback_submitted);
+    ```
+    **/
+    pub fn modal<M, F, Fut>(&mut self, action: F) -> &mut Self
+    where
+        M: Modal + 'static,
+        F: Fn(ModalContext, M) -> Fut + 'static,
+        Fut: Future + 'static,
+        Fut::Output: IntoResponseResult,
+    {
+        self.modals.push(ModalSubmit::new(action));
+        self
+    }
+
+    /**
+    Register error handler for [`InteractionHandler`].
+    It is called when a handler returns error, the command is not found or the options are invalid.
+    The default handler responds an ephemeral message.
+
+    # Example
+
+    ```ignore
+    InteractionHandler::builder().on_error(|err, _interaction| async move {
+        InteractionResponseBuilder::new()
+            .content(format!("failed: {}", err))
+            .flag(MessageFlags::EPHEMERAL)
+            .build(InteractionResponseType::ChannelMessageWithSource)
+    });
+    ```
+    **/
+    pub fn on_error<F, Fut>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(Error, Interaction) -> Fut + 'static,
+        Fut: Future<Output = InteractionResponse> + 'static,
+    {
+        self.error_handler = Rc::new(move |err, interaction| Box::pin(handler(err, interaction)));
+        self
+    }
+
+    /**
+    Register rate limit bucket for [`HttpClient`](crate::http::HttpClient).
+    The default bucket is stored in memory of the isolate, so use [`KvRateLimitBucket`](crate::http::KvRateLimitBucket)
+    or your own bucket to share it across worker invocations.
+    **/
+    pub fn rate_limit_bucket<B>(&mut self, bucket: B) -> &mut Self
+    where
+        B: RateLimitBucket + 'static,
+    {
+        self.rate_limit_bucket = Rc::new(bucket);
+        self
+    }
+
+    /**
+    Set the maximum difference between the request timestamp and the current time.
+    `None` disables the timestamp check. The default is 5 minutes.
+    **/
+    pub fn max_clock_skew(&mut self, skew: Option<Duration>) -> &mut Self {
+        self.max_clock_skew = skew;
+        self
+    }
+
+    /**
+    Register a store of processed interaction ids to reject replayed requests.
+    Use [`MemoryReplayStore`](crate::verify::MemoryReplayStore), [`KvReplayStore`](crate::verify::KvReplayStore)
+    or [`DurableObjectReplayStore`](crate::verify::DurableObjectReplayStore).
+    **/
+    pub fn replay_store<S>(&mut self, store: S) -> &mut Self
+    where
+        S: ReplayStore + 'static,
+    {
+        self.replay_store = Some(Rc::new(store));
+        self
+    }
+
+    /**
+    Register the translation catalog used by [`ChatInputCommandContext::translate`](crate::application_command::ChatInputCommandContext::translate).
+    Create it with `catalog!` macro.
+    **/
+    pub fn catalog(&mut self, catalog: Catalog) -> &mut Self {
+        self.catalog = Rc::new(catalog);
+        self
+    }
+
+    /**
+    Set the message to edit the original response with when deferred work returns an error.
+    **/
+    pub fn defer_failure_message(&mut self, message: &str) -> &mut Self {
+        self.defer_options.failure_message = message.to_string();
+        self
+    }
+
+    /**
+    Warn when deferred work is still running after `duration`. The default is 12 minutes.
+    Interaction tokens expire in 15 minutes, and the original response can't be edited after that.
+    **/
+    pub fn defer_warn_after(&mut self, duration: Duration) -> &mut Self {
+        self.defer_options.warn_after = duration;
+        self
+    }
+
+    /**
+    Register a middleware for all commands. Middlewares run in the order of registration.
+    See [`middleware`](crate::middleware) module.
+    **/
+    pub fn middleware<M>(&mut self, middleware: M) -> &mut Self
+    where
+        M: Middleware + 'static,
+    {
+        self.middlewares.push(Rc::new(middleware));
+        self
+    }
+
+    /**
+    Register a middleware for the command or the group of the name.
+    It runs after the global middlewares, and applies to all subcommands of the group.
+    **/
+    pub fn command_middleware<M>(&mut self, name: &str, middleware: M) -> &mut Self
+    where
+        M: Middleware + 'static,
+    {
+        self.command_middlewares
+            .entry(name.to_string())
+            .or_default()
+            .push(Rc::new(middleware));
+        self
+    }
+
+    /**
+    Register the store of cooldowns.
+    The default store is in memory of the isolate, so use [`KvCooldownStore`](crate::cooldown::KvCooldownStore)
+    or [`DurableObjectCooldownStore`](crate::cooldown::DurableObjectCooldownStore) to share the cooldowns across isolates.
+    **/
+    pub fn cooldown_store<S>(&mut self, store: S) -> &mut Self
+    where
+        S: CooldownStore + 'static,
+    {
+        self.cooldown_store = Rc::new(store);
+        self
+    }
+
+    /**
+    Set the ephemeral message when a cooldown is hit. `{seconds}` is replaced with the seconds to wait.
+    **/
+    pub fn cooldown_message(&mut self, message: &str) -> &mut Self {
+        self.cooldown_message = message.to_string();
+        self
+    }
+
+    /**
+    Register the transport of [`HttpClient`](crate::http::HttpClient) for proxies, tests or other runtimes.
+    The default transport is the fetch API on workers.
+    **/
+    pub fn transport<T>(&mut self, transport: T) -> &mut Self
+    where
+        T: Transport + 'static,
+    {
+        self.transport = Some(Rc::new(transport));
+        self
+    }
+
+    /**
+    Set the settings of [`HttpClient`](crate::http::HttpClient) created by the handler.
+    **/
+    pub fn http_config(&mut self, config: HttpConfig) -> &mut Self {
+        self.http_config = config;
+        self
+    }
+
+    /**
+    Set the base URL of Discord API without the version, for example a rate limit proxy.
+    The default is `https://discord.com/api`.
+    **/
+    pub fn api_base_url(&mut self, base_url: &str) -> &mut Self {
+        self.http_config.base_url = base_url.to_string();
+        self
+    }
+
+    /**
+    Set the version of Discord API. The default is 10.
+    **/
+    pub fn api_version(&mut self, version: u8) -> &mut Self {
+        self.http_config.api_version = version;
+        self
+    }
+
+    /**
+    Append the suffix to the user agent of the requests to Discord API.
+    **/
+    pub fn user_agent_suffix(&mut self, suffix: &str) -> &mut Self {
+        self.http_config.user_agent_suffix = Some(suffix.to_string());
+        self
+    }
+
+    /**
+    Add a header sent with every request to Discord API.
+    **/
+    pub fn http_header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.http_config
+            .headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /**
+    Set the timeout of each request to Discord API. There is no timeout by default.
+    See [`HttpClientBuilder::timeout`](crate::http::HttpClientBuilder::timeout).
+    **/
+    pub fn http_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.http_config.timeout = Some(timeout);
+        self
+    }
+
     /**
     Register application public key to handler.
 
@@ -67,17 +354,37 @@ impl CommandHandlerBuilder {
 
     /**
     Build and return [`InteractionHandler`].
+    It fails if the options of a command or the structure of a group is invalid. See [`Command::validate`].
     **/
     pub fn build(&mut self) -> Result<InteractionHandler, Box<dyn std::error::Error>> {
+        for command in &self.commands {
+            command.validate()?;
+        }
+        for group in &self.groups {
+            group.validate()?;
+        }
         Ok(InteractionHandler {
             commands: self.commands.clone(),
             groups: self.groups.clone(),
-            public_key: PublicKey::from_bytes(&*hex::decode(
+            components: self.components.clone(),
+            modals: self.modals.clone(),
+            error_handler: self.error_handler.clone(),
+            rate_limit_bucket: self.rate_limit_bucket.clone(),
+            max_clock_skew: self.max_clock_skew,
+            replay_store: self.replay_store.clone(),
+            catalog: self.catalog.clone(),
+            defer_options: Rc::new(self.defer_options.clone()),
+            middlewares: self.middlewares.clone(),
+            command_middlewares: self.command_middlewares.clone(),
+            cooldown_store: self.cooldown_store.clone(),
+            cooldown_message: self.cooldown_message.clone(),
+            transport: self.transport.clone(),
+            http_config: self.http_config.clone(),
+            public_key: PublicKey::from_bytes(&hex::decode(
                 self.public_key.clone().unwrap().as_bytes(),
             )?)?,
             token: self.token.clone().unwrap_or_default(),
-            application_id: Id::from_str(&*self.application_id.clone().unwrap_or_default())
-                .unwrap(),
+            application_id: Id::from_str(&self.application_id.clone().unwrap_or_default()).unwrap(),
         })
     }
 }
@@ -0,0 +1,200 @@
+use twilight_model::application::component::button::ButtonStyle;
+use twilight_model::application::component::select_menu::SelectMenuOption;
+use twilight_model::application::component::{ActionRow, Button, Component, SelectMenu};
+use twilight_model::channel::ReactionType;
+
+pub const ACTION_ROW_LIMIT: usize = 5;
+pub const BUTTON_PER_ROW_LIMIT: usize = 5;
+
+/**
+A builder for an action row of message components.
+An action row can contain up to 5 buttons or one select menu.
+
+# Example
+
+```ignore
+ctx.message(|m| {
+    m.content("vote").action_row(|row| {
+        row.button(ButtonStyle::Success, |b| b.custom_id("vote:yes").label("Yes"))
+            .button(ButtonStyle::Danger, |b| b.custom_id("vote:no").label("No"))
+    })
+})
+```
+**/
+#[derive(Debug, Clone, Default)]
+pub struct ActionRowBuilder {
+    _components: Vec<Component>,
+}
+
+impl ActionRowBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn button<F>(&mut self, style: ButtonStyle, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut ButtonBuilder) -> &mut ButtonBuilder,
+    {
+        let mut builder = ButtonBuilder::new(style);
+        f(&mut builder);
+        self._components.push(Component::Button(builder.build()));
+        self
+    }
+
+    pub fn select_menu<T, F>(&mut self, custom_id: T, f: F) -> &mut Self
+    where
+        T: ToString,
+        F: FnOnce(&mut SelectMenuBuilder) -> &mut SelectMenuBuilder,
+    {
+        let mut builder = SelectMenuBuilder::new(custom_id);
+        f(&mut builder);
+        self._components
+            .push(Component::SelectMenu(builder.build()));
+        self
+    }
+
+    pub fn build(&self) -> Component {
+        Component::ActionRow(ActionRow {
+            components: self._components.clone(),
+        })
+    }
+}
+
+/**
+A builder for [`Button`].
+**/
+#[derive(Debug, Clone)]
+pub struct ButtonBuilder {
+    _button: Button,
+}
+
+impl ButtonBuilder {
+    pub fn new(style: ButtonStyle) -> Self {
+        Self {
+            _button: Button {
+                custom_id: None,
+                disabled: false,
+                emoji: None,
+                label: None,
+                style,
+                url: None,
+            },
+        }
+    }
+
+    pub fn custom_id<T: ToString>(&mut self, custom_id: T) -> &mut Self {
+        self._button.custom_id = Some(custom_id.to_string());
+        self
+    }
+
+    pub fn label<T: ToString>(&mut self, label: T) -> &mut Self {
+        self._button.label = Some(label.to_string());
+        self
+    }
+
+    pub fn emoji(&mut self, emoji: ReactionType) -> &mut Self {
+        self._button.emoji = Some(emoji);
+        self
+    }
+
+    pub fn url<T: ToString>(&mut self, url: T) -> &mut Self {
+        self._button.url = Some(url.to_string());
+        self
+    }
+
+    pub fn disabled(&mut self, disabled: bool) -> &mut Self {
+        self._button.disabled = disabled;
+        self
+    }
+
+    pub fn build(&self) -> Button {
+        self._button.clone()
+    }
+}
+
+/**
+A builder for [`SelectMenu`].
+**/
+#[derive(Debug, Clone)]
+pub struct SelectMenuBuilder {
+    _menu: SelectMenu,
+}
+
+impl SelectMenuBuilder {
+    pub fn new<T: ToString>(custom_id: T) -> Self {
+        Self {
+            _menu: SelectMenu {
+                custom_id: custom_id.to_string(),
+                disabled: false,
+                max_values: None,
+                min_values: None,
+                options: vec![],
+                placeholder: None,
+            },
+        }
+    }
+
+    pub fn placeholder<T: ToString>(&mut self, placeholder: T) -> &mut Self {
+        self._menu.placeholder = Some(placeholder.to_string());
+        self
+    }
+
+    pub fn min_values(&mut self, min_values: u8) -> &mut Self {
+        self._menu.min_values = Some(min_values);
+        self
+    }
+
+    pub fn max_values(&mut self, max_values: u8) -> &mut Self {
+        self._menu.max_values = Some(max_values);
+        self
+    }
+
+    pub fn disabled(&mut self, disabled: bool) -> &mut Self {
+        self._menu.disabled = disabled;
+        self
+    }
+
+    pub fn option<L: ToString, V: ToString>(&mut self, label: L, value: V) -> &mut Self {
+        self._menu.options.push(SelectMenuOption {
+            default: false,
+            description: None,
+            emoji: None,
+            label: label.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    pub fn raw_option(&mut self, option: SelectMenuOption) -> &mut Self {
+        self._menu.options.push(option);
+        self
+    }
+
+    pub fn build(&self) -> SelectMenu {
+        self._menu.clone()
+    }
+}
+
+/**
+Check the action row contains up to 5 buttons or only one select menu.
+**/
+pub(crate) fn validate_action_row(row: &Component) -> Result<(), String> {
+    let components = match row {
+        Component::ActionRow(row) => &row.components,
+        _ => return Err("top-level components must be action rows".to_string()),
+    };
+    let menus = components
+        .iter()
+        .filter(|c| matches!(c, Component::SelectMenu(_)))
+        .count();
+    if menus > 0 && components.len() > 1 {
+        return Err("an action row with a select menu can't contain other components".to_string());
+    }
+    if components.len() > BUTTON_PER_ROW_LIMIT {
+        return Err(format!(
+            "an action row can contain up to {} buttons",
+            BUTTON_PER_ROW_LIMIT
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use twilight_model::channel::embed::{
+    Embed, EmbedAuthor, EmbedField, EmbedFooter, EmbedImage, EmbedThumbnail,
+};
+use twilight_model::util::Timestamp;
+
+pub const EMBED_FIELD_LIMIT: usize = 25;
+pub const EMBED_TOTAL_LENGTH_LIMIT: usize = 6000;
+
+/**
+A builder for [`Embed`].
+
+# Example
+
+```ignore
+ctx.message(|m| {
+    m.embed(|e| {
+        e.title("Hello")
+            .description("world")
+            .color(0x5865f2)
+            .field("name", "value", true)
+    })
+})
+```
+**/
+#[derive(Debug, Clone)]
+pub struct EmbedBuilder {
+    _embed: Embed,
+}
+
+impl EmbedBuilder {
+    pub fn new() -> Self {
+        Self {
+            _embed: Embed {
+                author: None,
+                color: None,
+                description: None,
+                fields: vec![],
+                footer: None,
+                image: None,
+                kind: "rich".to_string(),
+                provider: None,
+                thumbnail: None,
+                timestamp: None,
+                title: None,
+                url: None,
+                video: None,
+            },
+        }
+    }
+
+    pub fn title<T: ToString>(&mut self, title: T) -> &mut Self {
+        self._embed.title = Some(title.to_string());
+        self
+    }
+
+    pub fn description<T: ToString>(&mut self, description: T) -> &mut Self {
+        self._embed.description = Some(description.to_string());
+        self
+    }
+
+    pub fn url<T: ToString>(&mut self, url: T) -> &mut Self {
+        self._embed.url = Some(url.to_string());
+        self
+    }
+
+    pub fn color(&mut self, color: u32) -> &mut Self {
+        self._embed.color = Some(color);
+        self
+    }
+
+    pub fn timestamp(&mut self, timestamp: Timestamp) -> &mut Self {
+        self._embed.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn field<N: ToString, V: ToString>(
+        &mut self,
+        name: N,
+        value: V,
+        inline: bool,
+    ) -> &mut Self {
+        self._embed.fields.push(EmbedField {
+            inline,
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    pub fn footer<T: ToString>(&mut self, text: T, icon_url: Option<&str>) -> &mut Self {
+        self._embed.footer = Some(EmbedFooter {
+            icon_url: icon_url.map(ToString::to_string),
+            proxy_icon_url: None,
+            text: text.to_string(),
+        });
+        self
+    }
+
+    pub fn author<T: ToString>(
+        &mut self,
+        name: T,
+        url: Option<&str>,
+        icon_url: Option<&str>,
+    ) -> &mut Self {
+        self._embed.author = Some(EmbedAuthor {
+            icon_url: icon_url.map(ToString::to_string),
+            name: name.to_string(),
+            proxy_icon_url: None,
+            url: url.map(ToString::to_string),
+        });
+        self
+    }
+
+    pub fn image<T: ToString>(&mut self, url: T) -> &mut Self {
+        self._embed.image = Some(EmbedImage {
+            height: None,
+            proxy_url: None,
+            url: url.to_string(),
+            width: None,
+        });
+        self
+    }
+
+    pub fn thumbnail<T: ToString>(&mut self, url: T) -> &mut Self {
+        self._embed.thumbnail = Some(EmbedThumbnail {
+            height: None,
+            proxy_url: None,
+            url: url.to_string(),
+            width: None,
+        });
+        self
+    }
+
+    pub fn build(&self) -> Embed {
+        self._embed.clone()
+    }
+}
+
+impl Default for EmbedBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+Count the characters of the embed which Discord limits to 6000 in a message.
+**/
+pub fn embed_length(embed: &Embed) -> usize {
+    let len = |s: &Option<String>| s.as_ref().map_or(0, |s| s.chars().count());
+    len(&embed.title)
+        + len(&embed.description)
+        + embed.author.as_ref().map_or(0, |a| a.name.chars().count())
+        + embed.footer.as_ref().map_or(0, |f| f.text.chars().count())
+        + embed
+            .fields
+            .iter()
+            .map(|f| f.name.chars().count() + f.value.chars().count())
+            .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed_length() {
+        let embed = EmbedBuilder::new()
+            .title("abc")
+            .description("de")
+            .field("f", "gh", false)
+            .footer("ijk", None)
+            .build();
+        assert_eq!(embed_length(&embed), 11);
+        assert_eq!(embed.kind, "rich");
+    }
+}
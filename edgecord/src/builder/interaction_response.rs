@@ -1,11 +1,27 @@
+use crate::builder::component::{validate_action_row, ActionRowBuilder, ACTION_ROW_LIMIT};
+use crate::builder::embed::{
+    embed_length, EmbedBuilder, EMBED_FIELD_LIMIT, EMBED_TOTAL_LENGTH_LIMIT,
+};
+use crate::Error;
+use twilight_model::application::component::Component;
+use twilight_model::channel::embed::Embed;
+use twilight_model::channel::message::allowed_mentions::AllowedMentions;
 use twilight_model::channel::message::MessageFlags;
+use twilight_model::http::attachment::Attachment;
 use twilight_model::http::interaction::{
     InteractionResponse, InteractionResponseData, InteractionResponseType,
 };
 
+pub const EMBED_LIMIT: usize = 10;
+
 pub struct InteractionResponseBuilder {
     _content: String,
     _flags: MessageFlags,
+    _embeds: Vec<Embed>,
+    _components: Vec<Component>,
+    _allowed_mentions: Option<AllowedMentions>,
+    _tts: bool,
+    _attachments: Vec<Attachment>,
 }
 
 impl InteractionResponseBuilder {
@@ -13,6 +29,11 @@ impl InteractionResponseBuilder {
         Self {
             _content: String::default(),
             _flags: MessageFlags::empty(),
+            _embeds: vec![],
+            _components: vec![],
+            _allowed_mentions: None,
+            _tts: false,
+            _attachments: vec![],
         }
     }
 
@@ -29,33 +50,185 @@ impl InteractionResponseBuilder {
         self
     }
 
+    /**
+    Add an embed built by [`EmbedBuilder`].
+    **/
+    pub fn embed<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut EmbedBuilder) -> &mut EmbedBuilder,
+    {
+        let mut builder = EmbedBuilder::new();
+        f(&mut builder);
+        self._embeds.push(builder.build());
+        self
+    }
+
+    pub fn raw_embed(&mut self, embed: Embed) -> &mut Self {
+        self._embeds.push(embed);
+        self
+    }
+
+    /**
+    Add an action row built by [`ActionRowBuilder`].
+    **/
+    pub fn action_row<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut ActionRowBuilder) -> &mut ActionRowBuilder,
+    {
+        let mut builder = ActionRowBuilder::new();
+        f(&mut builder);
+        self._components.push(builder.build());
+        self
+    }
+
+    pub fn raw_component(&mut self, component: Component) -> &mut Self {
+        self._components.push(component);
+        self
+    }
+
+    pub fn allowed_mentions(&mut self, allowed_mentions: AllowedMentions) -> &mut Self {
+        self._allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    pub fn tts(&mut self, tts: bool) -> &mut Self {
+        self._tts = tts;
+        self
+    }
+
+    /**
+    Add an attachment. The file is uploaded with the response and can be referred from embeds by `attachment://filename`.
+    **/
+    pub fn attachment(&mut self, attachment: Attachment) -> &mut Self {
+        self._attachments.push(attachment);
+        self
+    }
+
+    /**
+    Check the response is in Discord's limits.
+    **/
+    pub fn validate(&self) -> crate::Result<()> {
+        validate_message(&self._embeds, &self._components)
+    }
+
+    /**
+    Build the response after validating it.
+    **/
+    pub fn try_build(&self, kind: InteractionResponseType) -> crate::Result<InteractionResponse> {
+        Ok(InteractionResponse {
+            kind,
+            data: Some(self.try_build_data()?),
+        })
+    }
+
+    /**
+    Validate and build the message data. It is also used for followup messages.
+    **/
+    pub fn try_build_data(&self) -> crate::Result<InteractionResponseData> {
+        self.validate()?;
+        Ok(self.build_data())
+    }
+
+    fn build_data(&self) -> InteractionResponseData {
+        InteractionResponseData {
+            allowed_mentions: self._allowed_mentions.clone(),
+            attachments: if self._attachments.is_empty() {
+                None
+            } else {
+                Some(self._attachments.clone())
+            },
+            choices: None,
+            components: if self._components.is_empty() {
+                None
+            } else {
+                Some(self._components.clone())
+            },
+            content: if self._content.is_empty() {
+                None
+            } else {
+                Some(self._content.clone())
+            },
+            custom_id: None,
+            embeds: if self._embeds.is_empty() {
+                None
+            } else {
+                Some(self._embeds.clone())
+            },
+            flags: if self._flags.is_empty() {
+                None
+            } else {
+                Some(self._flags)
+            },
+            title: None,
+            tts: if self._tts { Some(true) } else { None },
+        }
+    }
+
+    /**
+    Build the response without validating it.
+    [`InteractionHandler`](crate::InteractionHandler) validates the response returned by the handlers,
+    and passes the error to the error handler if it exceeds Discord's limits.
+    **/
     pub fn build(&self, kind: InteractionResponseType) -> InteractionResponse {
         InteractionResponse {
             kind,
-            data: Some(InteractionResponseData {
-                allowed_mentions: None,
-                attachments: None,
-                choices: None,
-                components: None,
-                content: if self._content.is_empty() {
-                    None
-                } else {
-                    Some(self._content.clone())
-                },
-                custom_id: None,
-                embeds: None,
-                flags: if self._flags.is_empty() {
-                    None
-                } else {
-                    Some(self._flags)
-                },
-                title: None,
-                tts: None,
-            }),
+            data: Some(self.build_data()),
         }
     }
 }
 
+/**
+Check the embeds and the components of a message are in Discord's limits.
+**/
+pub fn validate_message(embeds: &[Embed], components: &[Component]) -> crate::Result<()> {
+    if embeds.len() > EMBED_LIMIT {
+        return Err(Error::InvalidResponse(format!(
+            "a message can contain up to {} embeds",
+            EMBED_LIMIT
+        )));
+    }
+    if let Some(embed) = embeds
+        .iter()
+        .find(|embed| embed.fields.len() > EMBED_FIELD_LIMIT)
+    {
+        return Err(Error::InvalidResponse(format!(
+            "an embed can contain up to {} fields, but {} fields are given",
+            EMBED_FIELD_LIMIT,
+            embed.fields.len()
+        )));
+    }
+    let length = embeds.iter().map(embed_length).sum::<usize>();
+    if length > EMBED_TOTAL_LENGTH_LIMIT {
+        return Err(Error::InvalidResponse(format!(
+            "embeds can contain up to {} characters, but {} characters are given",
+            EMBED_TOTAL_LENGTH_LIMIT, length
+        )));
+    }
+    if components.len() > ACTION_ROW_LIMIT {
+        return Err(Error::InvalidResponse(format!(
+            "a message can contain up to {} action rows",
+            ACTION_ROW_LIMIT
+        )));
+    }
+    for row in components {
+        validate_action_row(row).map_err(Error::InvalidResponse)?;
+    }
+    Ok(())
+}
+
+/**
+Check the data of the response is in Discord's limits.
+**/
+pub fn validate_response(response: &InteractionResponse) -> crate::Result<()> {
+    match &response.data {
+        Some(data) => validate_message(
+            data.embeds.as_deref().unwrap_or_default(),
+            data.components.as_deref().unwrap_or_default(),
+        ),
+        None => Ok(()),
+    }
+}
+
 impl Default for InteractionResponseBuilder {
     fn default() -> Self {
         Self::new()
@@ -64,7 +237,8 @@ impl Default for InteractionResponseBuilder {
 
 #[cfg(test)]
 mod tests {
-    use crate::builder::InteractionResponseBuilder;
+    use crate::builder::{validate_response, InteractionResponseBuilder};
+    use twilight_model::application::component::button::ButtonStyle;
     use twilight_model::http::interaction::{
         InteractionResponse, InteractionResponseData, InteractionResponseType,
     };
@@ -93,4 +267,52 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn test_validate_limits() {
+        let mut builder = InteractionResponseBuilder::new();
+        builder.embed(|e| {
+            for i in 0..26 {
+                e.field(i, "value", false);
+            }
+            e
+        });
+        assert!(builder
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("fields"));
+
+        let mut builder = InteractionResponseBuilder::new();
+        for _ in 0..11 {
+            builder.embed(|e| e.title("a"));
+        }
+        assert!(builder
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("embeds"));
+
+        let mut builder = InteractionResponseBuilder::new();
+        builder.embed(|e| e.description("a".repeat(6001)));
+        assert!(builder.validate().is_err());
+
+        let mut builder = InteractionResponseBuilder::new();
+        for _ in 0..6 {
+            builder.action_row(|row| row.button(ButtonStyle::Primary, |b| b.custom_id("a")));
+        }
+        assert!(builder
+            .try_build(InteractionResponseType::ChannelMessageWithSource)
+            .is_err());
+    }
+
+    #[test]
+    fn test_build_does_not_validate() {
+        let mut builder = InteractionResponseBuilder::new();
+        for _ in 0..11 {
+            builder.embed(|e| e.title("a"));
+        }
+        let response = builder.build(InteractionResponseType::ChannelMessageWithSource);
+        assert!(validate_response(&response).is_err());
+    }
 }
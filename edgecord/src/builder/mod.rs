@@ -1,5 +1,9 @@
 mod command_handler;
+mod component;
+mod embed;
 mod interaction_response;
 
 pub use command_handler::*;
+pub use component::*;
+pub use embed::*;
 pub use interaction_response::*;
@@ -0,0 +1,422 @@
+/*!
+Cooldowns of commands.
+
+Add `cooldown(rate = 1, per = "30s", scope = "user")` to `#[command]` or `#[group]`.
+The handler counts the invocations in [`CooldownStore`] before invoking the command,
+and responds an ephemeral message if the limit is hit.
+
+The default store is in memory of the isolate and shared by all handlers in it.
+Use [`KvCooldownStore`] or [`DurableObjectCooldownStore`] to share the cooldowns across isolates.
+*/
+use crate::http::now_millis;
+use crate::log;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+use twilight_model::application::interaction::ApplicationCommand;
+use worker::kv::KvStore;
+use worker::ObjectNamespace;
+
+/**
+The unit that a cooldown is counted by.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CooldownScope {
+    User,
+    Guild,
+    Channel,
+    Global,
+}
+
+/**
+Allow `rate` invocations every `per` in the scope.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cooldown {
+    pub rate: u32,
+    pub per: Duration,
+    pub scope: CooldownScope,
+}
+
+impl Cooldown {
+    /**
+    The key of the bucket for the interaction. `name` is the name of the command or group.
+    In DMs, the guild scope is counted by the channel.
+    **/
+    pub fn key(&self, name: &str, interaction: &ApplicationCommand) -> String {
+        let id = match self.scope {
+            CooldownScope::User => interaction.author_id().map(|x| x.to_string()),
+            CooldownScope::Guild => interaction
+                .guild_id
+                .map(|x| x.to_string())
+                .or_else(|| Some(format!("dm-{}", interaction.channel_id))),
+            CooldownScope::Channel => Some(interaction.channel_id.to_string()),
+            CooldownScope::Global => Some("global".to_string()),
+        };
+        format!("{}:{:?}:{}", name, self.scope, id.unwrap_or_default())
+    }
+}
+
+/**
+The count of invocations in the current window.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CooldownBucket {
+    pub count: u32,
+    /// Unix time in milliseconds.
+    pub reset_at: u64,
+}
+
+impl CooldownBucket {
+    /**
+    Count an invocation at `now`. Returns the new bucket and the time to wait if the limit is hit.
+    **/
+    pub fn hit(
+        bucket: Option<CooldownBucket>,
+        now: u64,
+        rate: u32,
+        per: Duration,
+    ) -> (CooldownBucket, Option<Duration>) {
+        let bucket = match bucket {
+            Some(bucket) if bucket.reset_at > now => bucket,
+            _ => CooldownBucket {
+                count: 0,
+                reset_at: now + per.as_millis() as u64,
+            },
+        };
+        if bucket.count >= rate {
+            (bucket, Some(Duration::from_millis(bucket.reset_at - now)))
+        } else {
+            (
+                CooldownBucket {
+                    count: bucket.count + 1,
+                    ..bucket
+                },
+                None,
+            )
+        }
+    }
+
+    /**
+    The time to wait at `now` if the limit is hit. The invocation is not counted.
+    **/
+    pub fn wait(bucket: Option<CooldownBucket>, now: u64, rate: u32) -> Option<Duration> {
+        match bucket {
+            Some(bucket) if bucket.reset_at > now && bucket.count >= rate => {
+                Some(Duration::from_millis(bucket.reset_at - now))
+            }
+            _ => None,
+        }
+    }
+}
+
+/**
+A store of cooldown buckets.
+**/
+#[async_trait(?Send)]
+pub trait CooldownStore {
+    /**
+    Count an invocation of the key.
+    Returns the time to wait if the limit is hit.
+    **/
+    async fn hit(&self, key: &str, rate: u32, per: Duration) -> Option<Duration>;
+
+    /**
+    Returns the time to wait if the limit is hit, without counting an invocation.
+    The handler checks every cooldown of the command before counting them.
+    The default implementation never waits, so the limits are checked only by [`CooldownStore::hit`].
+    **/
+    async fn check(&self, _key: &str, _rate: u32) -> Option<Duration> {
+        None
+    }
+}
+
+thread_local! {
+    /// Workers run each isolate in a single thread, so this is shared by all requests of the isolate.
+    static MEMORY_BUCKETS: RefCell<HashMap<String, CooldownBucket>> = RefCell::new(HashMap::new());
+}
+
+/**
+A cooldown store in memory of the isolate.
+All memory stores share the buckets, so it works even if the handler is built for each request.
+The isolates are not shared across locations and can be evicted at any time.
+**/
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCooldownStore;
+
+impl MemoryCooldownStore {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait(?Send)]
+impl CooldownStore for MemoryCooldownStore {
+    async fn hit(&self, key: &str, rate: u32, per: Duration) -> Option<Duration> {
+        let now = now_millis();
+        MEMORY_BUCKETS.with(|buckets| {
+            let mut buckets = buckets.borrow_mut();
+            buckets.retain(|_, bucket| bucket.reset_at > now);
+            let (bucket, wait) = CooldownBucket::hit(buckets.get(key).copied(), now, rate, per);
+            buckets.insert(key.to_string(), bucket);
+            wait
+        })
+    }
+
+    async fn check(&self, key: &str, rate: u32) -> Option<Duration> {
+        let now = now_millis();
+        MEMORY_BUCKETS
+            .with(|buckets| CooldownBucket::wait(buckets.borrow().get(key).copied(), now, rate))
+    }
+}
+
+/**
+A cooldown store in Workers KV.
+KV is eventually consistent, so the count may be lost by invocations in a short time from other locations.
+**/
+#[derive(Clone)]
+pub struct KvCooldownStore {
+    store: KvStore,
+    prefix: String,
+}
+
+impl KvCooldownStore {
+    pub fn new(store: KvStore) -> Self {
+        Self {
+            store,
+            prefix: "cooldown:".to_string(),
+        }
+    }
+
+    pub fn with_prefix(store: KvStore, prefix: &str) -> Self {
+        Self {
+            store,
+            prefix: prefix.to_string(),
+        }
+    }
+
+    async fn get(&self, key: &str) -> Option<CooldownBucket> {
+        self.store
+            .get(key)
+            .json::<CooldownBucket>()
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+#[async_trait(?Send)]
+impl CooldownStore for KvCooldownStore {
+    async fn hit(&self, key: &str, rate: u32, per: Duration) -> Option<Duration> {
+        let key = format!("{}{}", self.prefix, key);
+        let now = now_millis();
+        let bucket = self.get(&key).await;
+        let (bucket, wait) = CooldownBucket::hit(bucket, now, rate, per);
+        if wait.is_none() {
+            // KV requires at least 60 seconds for ttl
+            let ttl = ((bucket.reset_at - now) / 1000).max(60);
+            let result = match self.store.put(&key, bucket) {
+                Ok(builder) => builder.expiration_ttl(ttl).execute().await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                log::error(&format!("failed to save the cooldown `{}`: {}", key, err));
+            }
+        }
+        wait
+    }
+
+    async fn check(&self, key: &str, rate: u32) -> Option<Duration> {
+        let key = format!("{}{}", self.prefix, key);
+        CooldownBucket::wait(self.get(&key).await, now_millis(), rate)
+    }
+}
+
+/**
+A cooldown store in Durable Objects. Each key is counted by its own object, so the count is strict.
+
+The Durable Object has to respond with [`handle_cooldown_request`].
+
+```ignore
+#[durable_object]
+pub struct Cooldowns {
+    state: State,
+}
+
+#[durable_object]
+impl DurableObject for Cooldowns {
+    fn new(state: State, _env: Env) -> Self {
+        Self { state }
+    }
+
+    async fn fetch(&mut self, req: Request) -> Result<Response> {
+        edgecord::cooldown::handle_cooldown_request(&mut self.state.storage(), req).await
+    }
+}
+
+InteractionHandler::builder()
+    .cooldown_store(DurableObjectCooldownStore::new(env.durable_object("COOLDOWNS")?));
+```
+**/
+pub struct DurableObjectCooldownStore {
+    namespace: ObjectNamespace,
+}
+
+impl DurableObjectCooldownStore {
+    pub fn new(namespace: ObjectNamespace) -> Self {
+        Self { namespace }
+    }
+
+    /**
+    Send the bucket to the object of the key. If `check` is true, the invocation is not counted.
+    **/
+    async fn fetch(&self, key: &str, rate: u32, per: Duration, check: bool) -> Option<Duration> {
+        let mut params = vec![
+            ("key", key.to_string()),
+            ("rate", rate.to_string()),
+            ("per", per.as_millis().to_string()),
+        ];
+        if check {
+            params.push(("check", "true".to_string()));
+        }
+        let url = url::Url::parse_with_params("https://cooldown/", &params).ok()?;
+        let stub = self.namespace.id_from_name(key).ok()?.get_stub().ok()?;
+        let mut response = stub.fetch_with_str(url.as_str()).await.ok()?;
+        let result = response.json::<CooldownResult>().await.ok()?;
+        result.wait.map(Duration::from_millis)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CooldownResult {
+    /// Milliseconds to wait.
+    wait: Option<u64>,
+}
+
+#[async_trait(?Send)]
+impl CooldownStore for DurableObjectCooldownStore {
+    async fn hit(&self, key: &str, rate: u32, per: Duration) -> Option<Duration> {
+        self.fetch(key, rate, per, false).await
+    }
+
+    async fn check(&self, key: &str, rate: u32) -> Option<Duration> {
+        self.fetch(key, rate, Duration::ZERO, true).await
+    }
+}
+
+/**
+Respond to a request from [`DurableObjectCooldownStore`] in the Durable Object.
+A request with `check` parameter only checks the bucket without counting the invocation.
+A request with missing or malformed parameters is responded with 400.
+**/
+pub async fn handle_cooldown_request(
+    storage: &mut worker::Storage,
+    req: worker::Request,
+) -> worker::Result<worker::Response> {
+    let params = req
+        .url()?
+        .query_pairs()
+        .into_owned()
+        .collect::<HashMap<_, _>>();
+    let (key, rate, per) = match parse_params(&params) {
+        Ok(params) => params,
+        Err(message) => return worker::Response::error(message, 400),
+    };
+
+    let bucket = storage.get::<CooldownBucket>(key).await.ok();
+    let now = now_millis();
+    let wait = if params.contains_key("check") {
+        CooldownBucket::wait(bucket, now, rate)
+    } else {
+        let (bucket, wait) = CooldownBucket::hit(bucket, now, rate, per);
+        if wait.is_none() {
+            storage.put(key, bucket).await?;
+        }
+        wait
+    };
+    worker::Response::from_json(&CooldownResult {
+        wait: wait.map(|x| x.as_millis() as u64),
+    })
+}
+
+/**
+Parse `key`, `rate` and `per` sent by [`DurableObjectCooldownStore`].
+**/
+fn parse_params(params: &HashMap<String, String>) -> Result<(&str, u32, Duration), String> {
+    let param = |name: &str| {
+        params
+            .get(name)
+            .ok_or_else(|| format!("missing parameter `{}`", name))
+    };
+    let invalid = |name: &str| format!("invalid parameter `{}`", name);
+    let key = param("key")?;
+    let rate = param("rate")?.parse::<u32>().map_err(|_| invalid("rate"))?;
+    let per = param("per")?.parse::<u64>().map_err(|_| invalid("per"))?;
+    Ok((key, rate, Duration::from_millis(per)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_hit() {
+        let per = Duration::from_secs(30);
+        let (bucket, wait) = CooldownBucket::hit(None, 1000, 2, per);
+        assert_eq!(wait, None);
+        let (bucket, wait) = CooldownBucket::hit(Some(bucket), 2000, 2, per);
+        assert_eq!(wait, None);
+        let (bucket, wait) = CooldownBucket::hit(Some(bucket), 3000, 2, per);
+        assert_eq!(wait, Some(Duration::from_millis(28000)));
+        assert_eq!(
+            CooldownBucket::wait(Some(bucket), 3000, 2),
+            Some(Duration::from_millis(28000))
+        );
+        assert_eq!(CooldownBucket::wait(Some(bucket), 3000, 3), None);
+        let (bucket, wait) = CooldownBucket::hit(Some(bucket), 31000, 2, per);
+        assert_eq!(wait, None);
+        assert_eq!(bucket.count, 1);
+        assert_eq!(CooldownBucket::wait(None, 31000, 1), None);
+    }
+
+    #[test]
+    fn test_memory_cooldown_store() {
+        let store = MemoryCooldownStore::new();
+        let per = Duration::from_secs(30);
+        assert!(futures::executor::block_on(store.hit("a", 1, per)).is_none());
+        assert!(futures::executor::block_on(store.hit("a", 1, per)).is_some());
+        assert!(futures::executor::block_on(store.hit("b", 1, per)).is_none());
+
+        // another store in the isolate shares the buckets
+        let store = MemoryCooldownStore::new();
+        assert!(futures::executor::block_on(store.hit("a", 1, per)).is_some());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let params = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        assert_eq!(
+            parse_params(&params(&[("key", "a"), ("rate", "2"), ("per", "3000")])),
+            Ok(("a", 2, Duration::from_secs(3)))
+        );
+        assert_eq!(
+            parse_params(&params(&[("key", "a"), ("rate", "x"), ("per", "3000")])),
+            Err("invalid parameter `rate`".to_string())
+        );
+        assert_eq!(
+            parse_params(&params(&[("key", "a"), ("rate", "2"), ("per", "-1")])),
+            Err("invalid parameter `per`".to_string())
+        );
+        assert_eq!(
+            parse_params(&params(&[("key", "a"), ("rate", "2")])),
+            Err("missing parameter `per`".to_string())
+        );
+    }
+}
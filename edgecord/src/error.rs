@@ -1,11 +1,46 @@
+use crate::verify::VerifyError;
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum Error {
     JsonValueError,
     WrongOptionType,
-    Forbidden,
-    NotFound,
-    HttpError(String),
+    /// The resolved object of the option is not in the interaction.
+    MissingResolved,
+    /// Discord API returned an error response.
+    Api {
+        status: u16,
+        code: ApiErrorCode,
+        message: String,
+        errors: Option<serde_json::Value>,
+    },
+    /// The request is rate limited and the retries are exhausted.
+    RateLimited {
+        retry_after: f64,
+        global: bool,
+    },
+    /// The bot token is invalid.
+    Unauthorized,
+    /// The request couldn't be sent.
+    Transport(String),
+    /// The request didn't complete within the timeout of [`HttpConfig`](crate::http::HttpConfig).
+    Timeout(std::time::Duration),
+    /// The response body couldn't be decoded.
+    Decode(String),
+    InvalidModalField(String),
+    CommandNotFound(String),
+    ComponentNotFound(String),
+    ModalNotFound(String),
+    MissingOption(String),
+    InvalidOption(String),
+    Handler(Box<dyn std::error::Error>),
+    InvalidResponse(String),
+    /// The command structure is rejected by Discord.
+    InvalidCommand(String),
+    /// The interaction request couldn't be verified.
+    Verification(VerifyError),
+    /// A `#[check]` of the command returned false.
+    CheckFailed(String),
 }
 
 impl std::fmt::Display for Error {
@@ -13,9 +48,151 @@ impl std::fmt::Display for Error {
         match self {
             Error::JsonValueError => write!(f, "parsing json value is out of i64 or u64"),
             Error::WrongOptionType => write!(f, "wrong option type was passed"),
-            Error::Forbidden => write!(f, "Bot don't have permission for the operation"),
-            Error::NotFound => write!(f, "The Endpoint is not found"),
-            Error::HttpError(err) => write!(f, "An Http Exception is raised: {}", err),
+            Error::MissingResolved => write!(f, "resolved data of the option is missing"),
+            Error::Api {
+                status,
+                code,
+                message,
+                ..
+            } => write!(
+                f,
+                "Discord API returned {} (code {}): {}",
+                status,
+                code.code(),
+                message
+            ),
+            Error::RateLimited {
+                retry_after,
+                global,
+            } => write!(
+                f,
+                "rate limited{}, retry after {} seconds",
+                if *global { " globally" } else { "" },
+                retry_after
+            ),
+            Error::Unauthorized => write!(f, "the bot token is invalid"),
+            Error::Transport(err) => write!(f, "failed to send the request: {}", err),
+            Error::Timeout(timeout) => {
+                write!(f, "the request timed out after {}ms", timeout.as_millis())
+            }
+            Error::Decode(err) => write!(f, "failed to decode the response: {}", err),
+            Error::InvalidModalField(name) => {
+                write!(f, "modal field `{}` is missing or invalid", name)
+            }
+            Error::CommandNotFound(name) => write!(f, "command `{}` is not found", name),
+            Error::ComponentNotFound(id) => write!(f, "component `{}` is not found", id),
+            Error::ModalNotFound(id) => write!(f, "modal `{}` is not found", id),
+            Error::MissingOption(name) => write!(f, "required option `{}` is missing", name),
+            Error::InvalidOption(name) => write!(f, "option `{}` has wrong type", name),
+            Error::Handler(err) => write!(f, "An error is raised in handler: {}", err),
+            Error::InvalidResponse(reason) => write!(f, "invalid response: {}", reason),
+            Error::InvalidCommand(reason) => write!(f, "invalid command: {}", reason),
+            Error::Verification(err) => write!(f, "verification failed: {}", err),
+            Error::CheckFailed(check) => write!(f, "check `{}` failed", check),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<VerifyError> for Error {
+    fn from(err: VerifyError) -> Self {
+        Error::Verification(err)
+    }
+}
+
+impl Error {
+    /**
+    Get the JSON error code if the error is returned from Discord API.
+    **/
+    pub fn api_code(&self) -> Option<ApiErrorCode> {
+        match self {
+            Error::Api { code, .. } => Some(*code),
+            _ => None,
         }
     }
 }
+
+macro_rules! api_error_codes {
+    ($($name:ident = $code:literal,)*) => {
+        /**
+        Discord JSON error codes.
+        See <https://discord.com/developers/docs/topics/opcodes-and-status-codes#json-json-error-codes>.
+        **/
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum ApiErrorCode {
+            $($name,)*
+            Other(u64),
+        }
+
+        impl ApiErrorCode {
+            pub fn code(&self) -> u64 {
+                match self {
+                    $(ApiErrorCode::$name => $code,)*
+                    ApiErrorCode::Other(code) => *code,
+                }
+            }
+        }
+
+        impl From<u64> for ApiErrorCode {
+            fn from(code: u64) -> Self {
+                match code {
+                    $($code => ApiErrorCode::$name,)*
+                    code => ApiErrorCode::Other(code),
+                }
+            }
+        }
+    };
+}
+
+api_error_codes! {
+    General = 0,
+    UnknownAccount = 10001,
+    UnknownApplication = 10002,
+    UnknownChannel = 10003,
+    UnknownGuild = 10004,
+    UnknownIntegration = 10005,
+    UnknownInvite = 10006,
+    UnknownMember = 10007,
+    UnknownMessage = 10008,
+    UnknownOverwrite = 10009,
+    UnknownRole = 10011,
+    UnknownToken = 10012,
+    UnknownUser = 10013,
+    UnknownEmoji = 10014,
+    UnknownWebhook = 10015,
+    UnknownInteraction = 10062,
+    UnknownApplicationCommand = 10063,
+    MaximumGuilds = 30001,
+    MaximumPins = 30003,
+    MaximumRoles = 30005,
+    MaximumApplicationCommandCreates = 30034,
+    Unauthorized = 40001,
+    InteractionAlreadyAcknowledged = 40060,
+    MissingAccess = 50001,
+    InvalidAccountType = 50002,
+    CannotExecuteOnDmChannel = 50003,
+    CannotEditMessageByOtherUser = 50005,
+    CannotSendEmptyMessage = 50006,
+    CannotSendMessagesToUser = 50007,
+    InvalidOauthState = 50012,
+    MissingPermissions = 50013,
+    InvalidAuthenticationToken = 50014,
+    InvalidWebhookToken = 50027,
+    InvalidFormBody = 50035,
+    ApplicationCommandPermissionsNotFound = 50068,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_code() {
+        assert_eq!(ApiErrorCode::from(10008), ApiErrorCode::UnknownMessage);
+        assert_eq!(ApiErrorCode::from(50001), ApiErrorCode::MissingAccess);
+        assert_eq!(ApiErrorCode::from(99999), ApiErrorCode::Other(99999));
+        assert_eq!(ApiErrorCode::MissingPermissions.code(), 50013);
+    }
+}
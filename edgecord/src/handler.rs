@@ -1,13 +1,128 @@
-use crate::application_command::{ChatInputCommandContext, Command, CommandGroup};
-use crate::builder::CommandHandlerBuilder;
-use crate::http::HttpClient;
+use crate::application_command::i18n::Catalog;
+use crate::application_command::DeferOptions;
+use crate::application_command::{
+    AutocompleteContext, ChatInputCommandContext, Command, CommandGroup, CommandOptionTree,
+};
+use crate::builder::{validate_response, CommandHandlerBuilder, InteractionResponseBuilder};
+use crate::cooldown::CooldownStore;
+use crate::http::now_millis;
+use crate::http::{Form, HttpClient, HttpConfig, RateLimitBucket, Transport};
+use crate::log;
+use crate::message_component::{Component, ComponentContext};
+use crate::middleware::{Middleware, Next};
+use crate::modal::{ModalContext, ModalSubmit};
+use crate::verify::{
+    check_timestamp, ReplayStore, VerifyError, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use crate::Error;
 use ed25519_dalek::{PublicKey, Signature, Verifier};
+use futures::future::LocalBoxFuture;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+use twilight_model::application::command::CommandType;
 use twilight_model::application::interaction::application_command::CommandDataOption;
-use twilight_model::application::interaction::{ApplicationCommand, Interaction};
-use twilight_model::http::interaction::{InteractionResponse, InteractionResponseType};
+use twilight_model::application::interaction::modal::ModalSubmitInteraction;
+use twilight_model::application::interaction::{
+    ApplicationCommand, ApplicationCommandAutocomplete, Interaction, MessageComponentInteraction,
+};
+use twilight_model::channel::message::MessageFlags;
+use twilight_model::http::interaction::{
+    InteractionResponse, InteractionResponseData, InteractionResponseType,
+};
 use twilight_model::id::marker::ApplicationMarker;
 use twilight_model::id::Id;
-use worker::{console_error, Response};
+use worker::Response;
+
+/// Seconds to keep processed interaction ids. Interaction tokens are valid for 15 minutes.
+const REPLAY_TTL: u64 = 15 * 60;
+
+pub type ErrorHandler =
+    Rc<dyn 'static + Fn(Error, Interaction) -> LocalBoxFuture<'static, InteractionResponse>>;
+
+/**
+Trait for the return value of handlers.
+Handlers can return either [`InteractionResponse`] or `Result<InteractionResponse, E>`.
+**/
+pub trait IntoResponseResult {
+    fn into_response_result(self) -> crate::Result<InteractionResponse>;
+}
+
+impl IntoResponseResult for InteractionResponse {
+    fn into_response_result(self) -> crate::Result<InteractionResponse> {
+        Ok(self)
+    }
+}
+
+impl<E> IntoResponseResult for Result<InteractionResponse, E>
+where
+    E: Into<Box<dyn std::error::Error>>,
+{
+    fn into_response_result(self) -> crate::Result<InteractionResponse> {
+        self.map_err(|err| Error::Handler(err.into()))
+    }
+}
+
+/**
+The default error handler. It responds an ephemeral message that describes the error.
+**/
+pub fn default_error_handler(
+    error: Error,
+    interaction: Interaction,
+) -> LocalBoxFuture<'static, InteractionResponse> {
+    Box::pin(async move {
+        if let Interaction::ApplicationCommandAutocomplete(_) = interaction {
+            return InteractionResponse {
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+                data: Some(InteractionResponseData {
+                    allowed_mentions: None,
+                    attachments: None,
+                    choices: Some(vec![]),
+                    components: None,
+                    content: None,
+                    custom_id: None,
+                    embeds: None,
+                    flags: None,
+                    title: None,
+                    tts: None,
+                }),
+            };
+        }
+        let content = match error {
+            Error::CommandNotFound(_) | Error::ComponentNotFound(_) | Error::ModalNotFound(_) => {
+                "This interaction is not available now.".to_string()
+            }
+            Error::MissingOption(_) | Error::InvalidOption(_) | Error::InvalidModalField(_) => {
+                format!("Invalid input: {}", error)
+            }
+            Error::CheckFailed(_) => "You can't use this command.".to_string(),
+            _ => "An error occurred while processing the interaction.".to_string(),
+        };
+        InteractionResponseBuilder::new()
+            .content(content)
+            .flag(MessageFlags::EPHEMERAL)
+            .build(InteractionResponseType::ChannelMessageWithSource)
+    })
+}
+
+/**
+Convert the response to worker response.
+If the response has attachments, it is sent as multipart/form-data with the files.
+**/
+fn to_worker_response(response: InteractionResponse) -> worker::Result<Response> {
+    let attachments = response
+        .data
+        .as_ref()
+        .and_then(|data| data.attachments.clone())
+        .unwrap_or_default();
+    if attachments.is_empty() {
+        return Response::from_json(&response);
+    }
+    let form = Form::with_attachments(&response, &attachments);
+    let mut headers = worker::Headers::new();
+    headers.set("Content-Type", &form.content_type())?;
+    Ok(Response::from_bytes(form.build())?.with_headers(headers))
+}
 
 /**
 A Discord Interaction Handler.
@@ -16,9 +131,25 @@ Parse Interaction and dispatch commands.
 pub struct InteractionHandler {
     pub commands: Vec<Command>,
     pub groups: Vec<CommandGroup>,
+    pub components: Vec<Component>,
+    pub modals: Vec<ModalSubmit>,
     pub public_key: PublicKey,
     pub token: String,
     pub application_id: Id<ApplicationMarker>,
+    pub error_handler: ErrorHandler,
+    pub rate_limit_bucket: Rc<dyn RateLimitBucket>,
+    pub max_clock_skew: Option<Duration>,
+    pub replay_store: Option<Rc<dyn ReplayStore>>,
+    pub catalog: Rc<Catalog>,
+    pub defer_options: Rc<DeferOptions>,
+    pub middlewares: Vec<Rc<dyn Middleware>>,
+    /// Middlewares for each top-level command or group name.
+    pub command_middlewares: HashMap<String, Vec<Rc<dyn Middleware>>>,
+    pub cooldown_store: Rc<dyn CooldownStore>,
+    /// The message when a cooldown is hit. `{seconds}` is replaced with the seconds to wait.
+    pub cooldown_message: String,
+    pub transport: Option<Rc<dyn Transport>>,
+    pub http_config: HttpConfig,
 }
 
 impl InteractionHandler {
@@ -45,79 +176,307 @@ impl InteractionHandler {
         env: worker::Env,
         ctx: worker::Context,
     ) -> worker::Result<worker::Response> {
-        if let Err(err) = self.verify(&req).await {
-            console_error!("verify error: {}", err.to_string());
-            return Response::error(err.to_string(), 401);
+        let header = |name: &str| req.headers().get(name).ok().flatten();
+        let signature = header(SIGNATURE_HEADER);
+        let timestamp = header(TIMESTAMP_HEADER);
+        let body = req.bytes().await?;
+        match self
+            .handle_payload(signature.as_deref(), timestamp.as_deref(), &body, env, ctx)
+            .await
+        {
+            Ok(Some(response)) => to_worker_response(response),
+            Ok(None) => Response::ok("ok"),
+            Err(err @ Error::Verification(_)) => {
+                log::error(&format!("verify error: {}", err));
+                Response::error(err.to_string(), 401)
+            }
+            Err(err) => Err(worker::Error::RustError(err.to_string())),
         }
-        let interaction = req.json::<Interaction>().await?;
+    }
 
-        match interaction {
-            Interaction::Ping(_ping) => worker::Response::from_json(&InteractionResponse {
+    /**
+    Verify the signed payload and dispatch the interaction.
+    It is [`process`](Self::process) without worker request and response, so it can be used on native targets.
+    Returns None if the interaction type is not supported.
+    **/
+    pub async fn handle_payload(
+        &self,
+        signature: Option<&str>,
+        timestamp: Option<&str>,
+        body: &[u8],
+        env: worker::Env,
+        ctx: worker::Context,
+    ) -> crate::Result<Option<InteractionResponse>> {
+        self.verify_signature(signature, timestamp, body)?;
+        let interaction = serde_json::from_slice::<Interaction>(body)
+            .map_err(|e| Error::Decode(e.to_string()))?;
+        self.check_replay(&interaction).await?;
+        Ok(self.dispatch(interaction, env, ctx).await)
+    }
+
+    /**
+    Dispatch the interaction to the handlers. Errors and responses over Discord's limits are passed to the error handler.
+    Returns None if the interaction type is not supported.
+    **/
+    pub async fn dispatch(
+        &self,
+        interaction: Interaction,
+        env: worker::Env,
+        ctx: worker::Context,
+    ) -> Option<InteractionResponse> {
+        let result = match interaction.clone() {
+            Interaction::Ping(_ping) => Ok(InteractionResponse {
                 kind: InteractionResponseType::Pong,
                 data: None,
             }),
             Interaction::ApplicationCommand(command) => {
-                self.handle_application_command(command, req, env, ctx)
-                    .await
+                self.handle_application_command(command, env, ctx).await
+            }
+            Interaction::ApplicationCommandAutocomplete(autocomplete) => {
+                self.handle_autocomplete(autocomplete, env, ctx).await
+            }
+            Interaction::MessageComponent(component) => {
+                self.handle_message_component(component, env, ctx).await
             }
-            _ => worker::Response::ok("ok"),
+            Interaction::ModalSubmit(modal) => self.handle_modal_submit(modal, env, ctx).await,
+            _ => return None,
+        };
+
+        match result.and_then(|response| validate_response(&response).map(|_| response)) {
+            Ok(response) => Some(response),
+            Err(err) => {
+                log::error(&err.to_string());
+                Some((self.error_handler)(err, interaction).await)
+            }
+        }
+    }
+
+    fn http(&self) -> HttpClient {
+        let mut builder = HttpClient::builder(&self.token, self.application_id);
+        builder
+            .config(self.http_config.clone())
+            .rate_limit_bucket(self.rate_limit_bucket.clone());
+        if let Some(transport) = &self.transport {
+            builder.transport(transport.clone());
         }
+        builder.build()
     }
 
     async fn handle_application_command(
         &self,
         command: Box<ApplicationCommand>,
-        _req: worker::Request,
         env: worker::Env,
         ctx: worker::Context,
-    ) -> worker::Result<worker::Response> {
-        match self.get_command(&command) {
-            None => {
-                console_error!("command not found");
-                panic!("command not found");
+    ) -> crate::Result<InteractionResponse> {
+        let (cmd, options) = self
+            .get_command(&command)
+            .ok_or_else(|| Error::CommandNotFound(command.data.name.clone()))?;
+        if let Some(wait) = self.hit_cooldowns(&cmd, &command).await {
+            return Ok(self.cooldown_response(wait));
+        }
+        let mut cmd_ctx = ChatInputCommandContext::new(command.clone(), env, ctx, self.http());
+        cmd_ctx.catalog = self.catalog.clone();
+        cmd_ctx.defer_options = self.defer_options.clone();
+        let middlewares = self
+            .middlewares
+            .iter()
+            .chain(
+                self.command_middlewares
+                    .get(&command.data.name)
+                    .into_iter()
+                    .flatten(),
+            )
+            .cloned()
+            .collect::<Vec<_>>();
+        Next::new(&middlewares, &cmd, command, options)
+            .run(cmd_ctx)
+            .await
+    }
+
+    /**
+    Count the invocation for the cooldowns of the group and the command.
+    Returns the time to wait if a cooldown is hit.
+    All cooldowns are checked before counting, so the group is not charged while the command is on cooldown.
+    **/
+    async fn hit_cooldowns(
+        &self,
+        cmd: &Command,
+        interaction: &ApplicationCommand,
+    ) -> Option<Duration> {
+        let group = self
+            .groups
+            .iter()
+            .find(|group| group.name == interaction.data.name);
+        let name = match group {
+            Some(group) => format!("{}/{}", group.name, cmd.name),
+            None => cmd.name.clone(),
+        };
+        let cooldowns = group
+            .and_then(|group| group.cooldown.map(|x| (x, group.name.clone())))
+            .into_iter()
+            .chain(cmd.cooldown.map(|x| (x, name)))
+            .map(|(cooldown, name)| (cooldown.key(&name, interaction), cooldown))
+            .collect::<Vec<_>>();
+        for (key, cooldown) in &cooldowns {
+            if let Some(wait) = self.cooldown_store.check(key, cooldown.rate).await {
+                return Some(wait);
             }
-            Some((cmd, options)) => {
-                let cmd_ctx = ChatInputCommandContext::new(
-                    command.clone(),
-                    env,
-                    ctx,
-                    HttpClient::new(&*self.token, self.application_id),
-                );
-                return cmd.invoke(cmd_ctx, command, options).await;
+        }
+        for (key, cooldown) in &cooldowns {
+            if let Some(wait) = self
+                .cooldown_store
+                .hit(key, cooldown.rate, cooldown.per)
+                .await
+            {
+                return Some(wait);
             }
         }
+        None
+    }
+
+    fn cooldown_response(&self, wait: Duration) -> InteractionResponse {
+        let seconds = (wait.as_millis() as f64 / 1000.0).ceil() as u64;
+        InteractionResponseBuilder::new()
+            .content(
+                self.cooldown_message
+                    .replace("{seconds}", &seconds.to_string()),
+            )
+            .flag(MessageFlags::EPHEMERAL)
+            .build(InteractionResponseType::ChannelMessageWithSource)
+    }
+
+    async fn handle_autocomplete(
+        &self,
+        interaction: Box<ApplicationCommandAutocomplete>,
+        env: worker::Env,
+        ctx: worker::Context,
+    ) -> crate::Result<InteractionResponse> {
+        let (cmd, options) = self
+            .find_command(
+                &interaction.data.name,
+                interaction.data.kind,
+                &interaction.data.options,
+            )
+            .ok_or_else(|| Error::CommandNotFound(interaction.data.name.clone()))?;
+        let autocomplete_ctx = AutocompleteContext::new(interaction, env, ctx, self.http());
+        Ok(cmd.invoke_autocomplete(autocomplete_ctx, options).await)
+    }
+
+    async fn handle_message_component(
+        &self,
+        interaction: Box<MessageComponentInteraction>,
+        env: worker::Env,
+        ctx: worker::Context,
+    ) -> crate::Result<InteractionResponse> {
+        let (component, params) = self
+            .get_component(&interaction.data.custom_id)
+            .ok_or_else(|| Error::ComponentNotFound(interaction.data.custom_id.clone()))?;
+        let component_ctx = ComponentContext::new(interaction, params, env, ctx, self.http());
+        component.invoke(component_ctx).await
+    }
+
+    async fn handle_modal_submit(
+        &self,
+        interaction: Box<ModalSubmitInteraction>,
+        env: worker::Env,
+        ctx: worker::Context,
+    ) -> crate::Result<InteractionResponse> {
+        let modal = self
+            .modals
+            .iter()
+            .find(|x| x.custom_id == interaction.data.custom_id)
+            .ok_or_else(|| Error::ModalNotFound(interaction.data.custom_id.clone()))?;
+        let modal_ctx = ModalContext::new(interaction, env, ctx, self.http());
+        modal.invoke(modal_ctx).await
     }
 
     /**
     Verify interaction and return verify result.
+    It checks the signature, the headers and the timestamp freshness.
     **/
-    pub async fn verify(&self, req: &worker::Request) -> Result<(), Box<dyn std::error::Error>> {
-        let signature = Signature::from_bytes(&*hex::decode(
-            req.headers().get("X-Signature-Ed25519")?.unwrap(),
-        )?)?;
-        let mut message = req
-            .headers()
-            .get("X-Signature-Timestamp")?
-            .unwrap()
-            .into_bytes();
-        message.append(&mut req.clone()?.bytes().await?);
+    pub async fn verify(&self, req: &worker::Request) -> crate::Result<()> {
+        let header = |name: &str| req.headers().get(name).ok().flatten();
+        let body = req
+            .clone()
+            .map_err(|e| Error::Transport(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| Error::Transport(e.to_string()))?;
+        self.verify_signature(
+            header(SIGNATURE_HEADER).as_deref(),
+            header(TIMESTAMP_HEADER).as_deref(),
+            &body,
+        )
+    }
+
+    /**
+    Verify the signature and the timestamp of the body.
+    **/
+    pub fn verify_signature(
+        &self,
+        signature: Option<&str>,
+        timestamp: Option<&str>,
+        body: &[u8],
+    ) -> crate::Result<()> {
+        let signature = hex::decode(signature.ok_or(VerifyError::MissingHeader(SIGNATURE_HEADER))?)
+            .ok()
+            .and_then(|bytes| Signature::from_bytes(&bytes).ok())
+            .ok_or(VerifyError::MalformedHeader(SIGNATURE_HEADER))?;
+        let timestamp = timestamp.ok_or(VerifyError::MissingHeader(TIMESTAMP_HEADER))?;
+        let mut message = timestamp.as_bytes().to_vec();
+        message.extend_from_slice(body);
 
         self.public_key
             .verify(message.as_slice(), &signature)
-            .map_err(|e| e.into())
+            .map_err(|_| VerifyError::InvalidSignature)?;
+
+        if let Some(max_skew) = self.max_clock_skew {
+            let timestamp = timestamp
+                .parse::<u64>()
+                .map_err(|_| VerifyError::MalformedHeader(TIMESTAMP_HEADER))?;
+            check_timestamp(timestamp, now_millis() / 1000, max_skew.as_secs())?;
+        }
+        Ok(())
+    }
+
+    /**
+    Reject the interaction if its id is already processed.
+    **/
+    async fn check_replay(&self, interaction: &Interaction) -> crate::Result<()> {
+        if let Some(store) = &self.replay_store {
+            let id = interaction.id().to_string();
+            if !store.insert(&id, REPLAY_TTL).await {
+                return Err(VerifyError::Replayed(id).into());
+            }
+        }
+        Ok(())
     }
 
     pub fn get_command(
         &self,
         command: &ApplicationCommand,
     ) -> Option<(Command, Vec<CommandDataOption>)> {
+        self.find_command(&command.data.name, command.data.kind, &command.data.options)
+    }
+
+    /**
+    Find the command by name and type from commands and groups.
+    Returns the command and its options.
+    **/
+    pub fn find_command<O>(
+        &self,
+        name: &str,
+        kind: CommandType,
+        options: &[O],
+    ) -> Option<(Command, Vec<O>)>
+    where
+        O: CommandOptionTree,
+    {
         for group in &self.groups {
-            if group.name != command.data.name {
+            if kind != CommandType::ChatInput || group.name != name {
                 continue;
             }
-            let commands = command
-                .data
-                .options
+            let commands = options
                 .iter()
                 .filter_map(|x| group.get_command(x.clone()))
                 .collect::<Vec<_>>();
@@ -128,8 +487,84 @@ impl InteractionHandler {
 
         self.commands
             .iter()
-            .find(|cmd| cmd.name == command.data.name)
+            .find(|cmd| cmd.command_type == kind && cmd.name == name)
             .cloned()
-            .map(|cmd| (cmd, command.data.options.clone()))
+            .map(|cmd| (cmd, options.to_vec()))
+    }
+
+    /**
+    Find the component handler for `custom_id`.
+    Exact custom ids take priority over prefixes and patterns.
+    **/
+    pub fn get_component(&self, custom_id: &str) -> Option<(Component, HashMap<String, String>)> {
+        self.components
+            .iter()
+            .filter(|x| x.custom_id.is_exact())
+            .chain(self.components.iter().filter(|x| !x.custom_id.is_exact()))
+            .find_map(|x| x.matches(custom_id).map(|params| (x.clone(), params)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application_command::{ChatInputCommandContext, SubCommand};
+    use crate::testing::{TestClient, TestInteraction};
+    use crate::{command, group};
+
+    #[command(
+        description = "once",
+        cooldown(rate = 1, per = "30s", scope = "global")
+    )]
+    async fn once(ctx: ChatInputCommandContext) -> InteractionResponse {
+        ctx.message(|msg| msg.content("once"))
+    }
+
+    #[command(description = "other")]
+    async fn other(ctx: ChatInputCommandContext) -> InteractionResponse {
+        ctx.message(|msg| msg.content("other"))
+    }
+
+    #[group(
+        description = "limited commands",
+        cooldown(rate = 2, per = "30s", scope = "global")
+    )]
+    fn limited() -> Vec<SubCommand> {
+        vec![SubCommand::Command(once()), SubCommand::Command(other())]
+    }
+
+    #[test]
+    fn test_into_response_result() {
+        let response = InteractionResponseBuilder::new()
+            .content("ok")
+            .build(InteractionResponseType::ChannelMessageWithSource);
+        assert!(response.clone().into_response_result().is_ok());
+
+        let result: Result<InteractionResponse, String> = Err("failed".to_string());
+        match result.into_response_result() {
+            Err(Error::Handler(err)) => assert_eq!(err.to_string(), "failed"),
+            _ => panic!("expected handler error"),
+        }
+    }
+
+    #[test]
+    fn test_group_cooldown() {
+        let mut builder = InteractionHandler::builder();
+        builder.group(limited()).cooldown_message("wait");
+        let client = TestClient::new(&mut builder);
+        let send = |name: &str| {
+            futures::executor::block_on(
+                client.send(TestInteraction::subcommand(&["limited", name])),
+            )
+            .unwrap()
+            .data
+            .and_then(|data| data.content)
+        };
+
+        assert_eq!(send("once").as_deref(), Some("once"));
+        // the group is not charged while the command is on cooldown
+        assert_eq!(send("once").as_deref(), Some("wait"));
+        assert_eq!(send("other").as_deref(), Some("other"));
+        assert_eq!(send("other").as_deref(), Some("wait"));
     }
 }
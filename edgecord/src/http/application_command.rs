@@ -0,0 +1,54 @@
+use crate::http::{required, HttpClient, Routes};
+use serde_json::Value;
+use twilight_model::id::marker::GuildMarker;
+use twilight_model::id::Id;
+use worker::Method;
+
+/**
+The scope of application commands.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandScope {
+    Global,
+    Guild(Id<GuildMarker>),
+}
+
+impl HttpClient {
+    fn commands_route(&self, scope: CommandScope) -> Routes {
+        match scope {
+            CommandScope::Global => Routes::ApplicationCommands(self.application_id),
+            CommandScope::Guild(guild_id) => {
+                Routes::ApplicationGuildCommands(self.application_id, guild_id)
+            }
+        }
+    }
+
+    /**
+    Fetch registered application commands as raw JSON, with all localizations of the names and descriptions.
+    **/
+    pub async fn get_application_commands(&self, scope: CommandScope) -> crate::Result<Vec<Value>> {
+        self.request_with_query::<_, Vec<Value>>(
+            Method::Get,
+            self.commands_route(scope),
+            &[("with_localizations", true)],
+        )
+        .await
+        .and_then(required)
+    }
+
+    /**
+    Overwrite all application commands in the scope.
+    **/
+    pub async fn bulk_overwrite_application_commands<B>(
+        &self,
+        scope: CommandScope,
+        commands: &B,
+    ) -> crate::Result<Vec<Value>>
+    where
+        B: serde::Serialize,
+    {
+        self.request::<&B, Vec<Value>>(Method::Put, self.commands_route(scope), Some(commands))
+            .await
+            .and_then(required)
+    }
+}
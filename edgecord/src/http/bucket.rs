@@ -1,5 +1,288 @@
-pub trait RateLimitBucket {}
+use crate::log;
+use async_trait::async_trait;
+use cfg_if::cfg_if;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+use worker::kv::KvStore;
+use worker::Method;
 
-pub struct DefaultRateLimitBucket {}
+pub(crate) const GLOBAL_BUCKET_KEY: &str = "global";
 
-impl RateLimitBucket for DefaultRateLimitBucket {}
+/**
+The state of a rate limit bucket.
+`reset_at` is unix time in milliseconds.
+**/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BucketState {
+    pub remaining: u64,
+    pub reset_at: u64,
+}
+
+impl BucketState {
+    /**
+    Milliseconds to wait before sending the next request.
+    **/
+    pub fn wait_millis(&self, now: u64) -> Option<u64> {
+        if self.remaining == 0 && self.reset_at > now {
+            Some(self.reset_at - now)
+        } else {
+            None
+        }
+    }
+}
+
+/**
+A storage of rate limit buckets.
+The keys are made from the route and its major parameters, and `global` is used for the global rate limit.
+
+Implement this trait with Workers KV or Durable Objects to share the state across worker invocations.
+**/
+#[async_trait(?Send)]
+pub trait RateLimitBucket {
+    async fn get(&self, key: &str) -> Option<BucketState>;
+    async fn put(&self, key: &str, state: BucketState);
+}
+
+thread_local! {
+    /// Workers run each isolate in a single thread, so this is shared by all requests of the isolate.
+    static MEMORY_STATES: RefCell<HashMap<String, BucketState>> = RefCell::new(HashMap::new());
+}
+
+/**
+A rate limit bucket stored in memory of the isolate.
+All default buckets share the states, so it works even if the handler is built for each request.
+The isolates are not shared across locations and can be evicted at any time.
+**/
+#[derive(Debug, Clone, Default)]
+pub struct DefaultRateLimitBucket;
+
+impl DefaultRateLimitBucket {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait(?Send)]
+impl RateLimitBucket for DefaultRateLimitBucket {
+    async fn get(&self, key: &str) -> Option<BucketState> {
+        MEMORY_STATES.with(|states| states.borrow().get(key).copied())
+    }
+
+    async fn put(&self, key: &str, state: BucketState) {
+        MEMORY_STATES.with(|states| {
+            states.borrow_mut().insert(key.to_string(), state);
+        });
+    }
+}
+
+/**
+A rate limit bucket stored in Workers KV.
+It is shared by all isolates, but KV is eventually consistent so it may be stale for a while.
+**/
+#[derive(Clone)]
+pub struct KvRateLimitBucket {
+    store: KvStore,
+    prefix: String,
+}
+
+impl KvRateLimitBucket {
+    pub fn new(store: KvStore) -> Self {
+        Self {
+            store,
+            prefix: "ratelimit:".to_string(),
+        }
+    }
+
+    pub fn with_prefix(store: KvStore, prefix: &str) -> Self {
+        Self {
+            store,
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl RateLimitBucket for KvRateLimitBucket {
+    async fn get(&self, key: &str) -> Option<BucketState> {
+        self.store
+            .get(&format!("{}{}", self.prefix, key))
+            .json::<BucketState>()
+            .await
+            .ok()
+            .flatten()
+    }
+
+    async fn put(&self, key: &str, state: BucketState) {
+        // KV requires at least 60 seconds for ttl
+        let ttl = ((state.reset_at.saturating_sub(now_millis())) / 1000).max(60);
+        let key = format!("{}{}", self.prefix, key);
+        let result = match self.store.put(&key, state) {
+            Ok(builder) => builder.expiration_ttl(ttl).execute().await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            log::error(&format!("failed to save the rate limit `{}`: {}", key, err));
+        }
+    }
+}
+
+/**
+Make the bucket key from the method and the path.
+Ids are replaced except the major parameters (channel, guild and webhook).
+Tokens of webhooks and interactions are hashed, so the keys can be stored without leaking them.
+**/
+pub(crate) fn bucket_key(method: &Method, path: &str) -> String {
+    let segments = path.split('/').collect::<Vec<_>>();
+    let key = segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let previous = if i > 0 { segments[i - 1] } else { "" };
+            let is_token = i > 1 && matches!(segments[i - 2], "webhooks" | "interactions");
+            if is_token {
+                format!(":token-{}", hash_token(segment))
+            } else if !matches!(previous, "channels" | "guilds" | "webhooks")
+                && !segment.is_empty()
+                && segment.chars().all(|c| c.is_ascii_digit())
+            {
+                ":id".to_string()
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{} {}", method.as_ref(), key)
+}
+
+fn hash_token(token: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    token.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/**
+Make the bucket state from `X-RateLimit-*` headers. The header names must be lowercase.
+**/
+pub(crate) fn parse_headers(headers: &HashMap<String, String>, now: u64) -> Option<BucketState> {
+    let remaining = headers.get("x-ratelimit-remaining")?.parse::<u64>().ok()?;
+    let reset_after = headers
+        .get("x-ratelimit-reset-after")?
+        .parse::<f64>()
+        .ok()?;
+    Some(BucketState {
+        remaining,
+        reset_at: now + (reset_after * 1000.0).ceil() as u64,
+    })
+}
+
+cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        pub(crate) fn now_millis() -> u64 {
+            worker::Date::now().as_millis()
+        }
+
+        pub(crate) async fn sleep(duration: Duration) -> crate::Result<()> {
+            use worker::js_sys::{Function, Promise, Reflect};
+            use worker::wasm_bindgen::JsValue;
+
+            let promise = Promise::new(&mut |resolve, _| {
+                let global = worker::js_sys::global();
+                let set_timeout = Function::from(
+                    Reflect::get(&global, &JsValue::from_str("setTimeout")).unwrap(),
+                );
+                set_timeout
+                    .call2(&global, &resolve, &JsValue::from_f64(duration.as_millis() as f64))
+                    .unwrap();
+            });
+            let _ = worker::wasm_bindgen_futures::JsFuture::from(promise).await;
+            Ok(())
+        }
+    } else {
+        pub(crate) fn now_millis() -> u64 {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default()
+        }
+
+        #[cfg(feature = "local")]
+        pub(crate) async fn sleep(duration: Duration) -> crate::Result<()> {
+            tokio::time::sleep(duration).await;
+            Ok(())
+        }
+
+        /// There is no timer without `local` feature. Blocking the thread would stall the executor.
+        #[cfg(not(feature = "local"))]
+        pub(crate) async fn sleep(_duration: Duration) -> crate::Result<()> {
+            Err(crate::Error::Transport(
+                "no timer is available, enable `local` feature or implement `Transport::sleep`"
+                    .to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_key() {
+        assert_eq!(
+            bucket_key(&Method::Get, "/channels/123/messages/456"),
+            "GET /channels/123/messages/:id"
+        );
+        let key = bucket_key(&Method::Post, "/webhooks/111/secret/messages/222");
+        assert!(key.starts_with("POST /webhooks/111/:token-"));
+        assert!(key.ends_with("/messages/:id"));
+        assert!(!key.contains("secret"));
+        assert_ne!(
+            key,
+            bucket_key(&Method::Post, "/webhooks/111/other/messages/222")
+        );
+        let key = bucket_key(&Method::Post, "/interactions/333/secret/callback");
+        assert!(key.starts_with("POST /interactions/:id/:token-"));
+        assert!(!key.contains("secret"));
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        let headers = HashMap::from([
+            ("x-ratelimit-remaining".to_string(), "0".to_string()),
+            ("x-ratelimit-reset-after".to_string(), "1.5".to_string()),
+        ]);
+        let state = parse_headers(&headers, 1000).unwrap();
+        assert_eq!(
+            state,
+            BucketState {
+                remaining: 0,
+                reset_at: 2500
+            }
+        );
+        assert_eq!(state.wait_millis(2000), Some(500));
+        assert_eq!(state.wait_millis(3000), None);
+    }
+
+    #[test]
+    fn test_default_bucket_shared() {
+        let state = BucketState {
+            remaining: 0,
+            reset_at: 2500,
+        };
+        futures::executor::block_on(async {
+            DefaultRateLimitBucket::new()
+                .put("GET /shared", state)
+                .await;
+            assert_eq!(
+                DefaultRateLimitBucket::new().get("GET /shared").await,
+                Some(state)
+            );
+            assert_eq!(DefaultRateLimitBucket::new().get("GET /other").await, None);
+        });
+    }
+}
@@ -0,0 +1,111 @@
+use crate::http::{HttpClient, Routes};
+use crate::model::rest::channel::{
+    CreateChannelInvite, CreateGuildChannel, EditChannelPermissions, FollowAnnouncementChannel,
+    ModifyChannel, ModifyGuildChannelPosition,
+};
+use twilight_model::channel::{Channel, FollowedChannel};
+use twilight_model::id::marker::{ChannelMarker, GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_model::invite::Invite;
+
+impl HttpClient {
+    rest_methods! {
+        fn get_channel(channel_id: Id<ChannelMarker>) -> Channel {
+            Get Routes::Channel(channel_id)
+        }
+
+        fn modify_channel(
+            channel_id: Id<ChannelMarker>,
+            body: &ModifyChannel,
+            reason: Option<&str>,
+        ) -> Channel {
+            Patch Routes::Channel(channel_id), body: body, reason: reason
+        }
+
+        /**
+        Delete a guild channel or close a DM.
+        **/
+        fn delete_channel(channel_id: Id<ChannelMarker>, reason: Option<&str>) -> Channel {
+            Delete Routes::Channel(channel_id), reason: reason
+        }
+
+        fn edit_channel_member_permissions(
+            channel_id: Id<ChannelMarker>,
+            user_id: Id<UserMarker>,
+            body: &EditChannelPermissions,
+            reason: Option<&str>,
+        ) {
+            Put Routes::ChannelPermissionsOverwriteMember(channel_id, user_id), body: body, reason: reason
+        }
+
+        fn edit_channel_role_permissions(
+            channel_id: Id<ChannelMarker>,
+            role_id: Id<RoleMarker>,
+            body: &EditChannelPermissions,
+            reason: Option<&str>,
+        ) {
+            Put Routes::ChannelPermissionsOverwriteRole(channel_id, role_id), body: body, reason: reason
+        }
+
+        fn delete_channel_member_permission(
+            channel_id: Id<ChannelMarker>,
+            user_id: Id<UserMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::ChannelPermissionsOverwriteMember(channel_id, user_id), reason: reason
+        }
+
+        fn delete_channel_role_permission(
+            channel_id: Id<ChannelMarker>,
+            role_id: Id<RoleMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::ChannelPermissionsOverwriteRole(channel_id, role_id), reason: reason
+        }
+
+        fn get_channel_invites(channel_id: Id<ChannelMarker>) -> Vec<Invite> {
+            Get Routes::ChannelInvites(channel_id)
+        }
+
+        fn create_channel_invite(
+            channel_id: Id<ChannelMarker>,
+            body: &CreateChannelInvite,
+            reason: Option<&str>,
+        ) -> Invite {
+            Post Routes::ChannelInvites(channel_id), body: body, reason: reason
+        }
+
+        /**
+        Follow the announcement channel to send its messages to the channel of `body`.
+        **/
+        fn follow_announcement_channel(
+            channel_id: Id<ChannelMarker>,
+            body: &FollowAnnouncementChannel,
+        ) -> FollowedChannel {
+            Post Routes::ChannelFollowers(channel_id), body: body
+        }
+
+        fn trigger_typing_indicator(channel_id: Id<ChannelMarker>) {
+            Post Routes::ChannelTyping(channel_id)
+        }
+
+        fn get_guild_channels(guild_id: Id<GuildMarker>) -> Vec<Channel> {
+            Get Routes::GuildChannels(guild_id)
+        }
+
+        fn create_guild_channel(
+            guild_id: Id<GuildMarker>,
+            body: &CreateGuildChannel,
+            reason: Option<&str>,
+        ) -> Channel {
+            Post Routes::GuildChannels(guild_id), body: body, reason: reason
+        }
+
+        fn modify_guild_channel_positions(
+            guild_id: Id<GuildMarker>,
+            body: &[ModifyGuildChannelPosition],
+        ) {
+            Patch Routes::GuildChannels(guild_id), body: body
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use super::transport::default_transport;
+use super::{DefaultRateLimitBucket, HttpClient, RateLimitBucket, Transport};
+use std::rc::Rc;
+use std::time::Duration;
+use twilight_model::id::marker::ApplicationMarker;
+use twilight_model::id::Id;
+
+const DEFAULT_BASE_URL: &str = "https://discord.com/api";
+const DEFAULT_API_VERSION: u8 = 10;
+const USER_AGENT: &str = "Discord Bot (https://github.com/sizumita/edgelord 0.0.1)";
+
+/**
+Settings of [`HttpClient`] requests.
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpConfig {
+    /// The base URL without the version, such as `http://localhost:3000/api` for a proxy.
+    pub base_url: String,
+    pub api_version: u8,
+    /// Appended to the user agent.
+    pub user_agent_suffix: Option<String>,
+    /// Sent with every request.
+    pub headers: Vec<(String, String)>,
+    /// The timeout of each request. The retries for rate limits have their own timeouts.
+    /// It uses [`Transport::sleep`], so native targets without `local` feature need a transport with a timer.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_version: DEFAULT_API_VERSION,
+            user_agent_suffix: None,
+            headers: vec![],
+            timeout: None,
+        }
+    }
+}
+
+impl HttpConfig {
+    /**
+    The base URL with the version, such as `https://discord.com/api/v10`.
+    **/
+    pub fn api_url(&self) -> String {
+        format!(
+            "{}/v{}",
+            self.base_url.trim_end_matches('/'),
+            self.api_version
+        )
+    }
+
+    pub fn user_agent(&self) -> String {
+        match &self.user_agent_suffix {
+            Some(suffix) => format!("{} {}", USER_AGENT, suffix),
+            None => USER_AGENT.to_string(),
+        }
+    }
+}
+
+/**
+A builder for [`HttpClient`].
+
+# Example
+
+```ignore
+let client = HttpClient::builder(&token, application_id)
+    .base_url("http://localhost:3000/api")
+    .timeout(Duration::from_secs(10))
+    .build();
+```
+**/
+pub struct HttpClientBuilder {
+    token: String,
+    application_id: Id<ApplicationMarker>,
+    config: HttpConfig,
+    bucket: Rc<dyn RateLimitBucket>,
+    transport: Rc<dyn Transport>,
+}
+
+impl HttpClientBuilder {
+    pub fn new(token: &str, application_id: Id<ApplicationMarker>) -> Self {
+        Self {
+            token: token.to_string(),
+            application_id,
+            config: HttpConfig::default(),
+            bucket: Rc::new(DefaultRateLimitBucket::new()),
+            transport: default_transport(),
+        }
+    }
+
+    /**
+    Replace all settings with the config.
+    **/
+    pub fn config(&mut self, config: HttpConfig) -> &mut Self {
+        self.config = config;
+        self
+    }
+
+    /**
+    Set the base URL without the version. The default is `https://discord.com/api`.
+    **/
+    pub fn base_url(&mut self, base_url: &str) -> &mut Self {
+        self.config.base_url = base_url.to_string();
+        self
+    }
+
+    /**
+    Set the API version. The default is 10.
+    **/
+    pub fn api_version(&mut self, version: u8) -> &mut Self {
+        self.config.api_version = version;
+        self
+    }
+
+    pub fn user_agent_suffix(&mut self, suffix: &str) -> &mut Self {
+        self.config.user_agent_suffix = Some(suffix.to_string());
+        self
+    }
+
+    /**
+    Add a header sent with every request.
+    **/
+    pub fn header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.config
+            .headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /**
+    Set the timeout of each request. It is measured with [`Transport::sleep`],
+    so native targets without `local` feature need a transport with a timer, or all requests fail.
+    **/
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    pub fn rate_limit_bucket(&mut self, bucket: Rc<dyn RateLimitBucket>) -> &mut Self {
+        self.bucket = bucket;
+        self
+    }
+
+    pub fn transport(&mut self, transport: Rc<dyn Transport>) -> &mut Self {
+        self.transport = transport;
+        self
+    }
+
+    pub fn build(&self) -> HttpClient {
+        HttpClient {
+            token: format!("Bot {}", self.token),
+            application_id: self.application_id,
+            config: Rc::new(self.config.clone()),
+            bucket: self.bucket.clone(),
+            transport: self.transport.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::transport::MockTransport;
+    use crate::http::Routes;
+    use worker::Method;
+
+    #[test]
+    fn test_builder() {
+        let mock = Rc::new(MockTransport::new());
+        let client = HttpClient::builder("token", Id::new(1))
+            .base_url("http://localhost:3000/api/")
+            .api_version(9)
+            .user_agent_suffix("test")
+            .header("X-Proxy", "1")
+            .transport(mock.clone())
+            .build();
+        futures::executor::block_on(client.request::<(), ()>(
+            Method::Delete,
+            Routes::Guild(Id::new(2)),
+            None,
+        ))
+        .unwrap();
+
+        let request = &mock.requests()[0];
+        assert_eq!(request.url, "http://localhost:3000/api/v9/guilds/2");
+        assert_eq!(request.header("x-proxy"), Some("1"));
+        assert!(request.header("user-agent").unwrap().ends_with(" test"));
+    }
+}
@@ -1,20 +1,281 @@
 use super::super::model::rest::webhook::WebhookBody;
-use crate::http::{HttpClient, Routes};
+use crate::builder::InteractionResponseBuilder;
+use crate::http::{now_millis, required, HttpClient, Routes};
+use crate::InteractionResponse;
+use std::time::Duration;
 use twilight_model::channel::Message;
+use twilight_model::id::marker::{InteractionMarker, MessageMarker};
+use twilight_model::id::Id;
 use worker::Method;
 
 impl HttpClient {
+    /**
+    Respond to the interaction with the callback endpoint.
+    If the response has attachments, their files are uploaded together.
+    **/
+    pub async fn create_interaction_response(
+        &self,
+        interaction_id: Id<InteractionMarker>,
+        interaction_token: String,
+        response: InteractionResponse,
+    ) -> crate::Result<()> {
+        let attachments = response
+            .data
+            .as_ref()
+            .and_then(|data| data.attachments.clone())
+            .unwrap_or_default();
+        self.request_multipart::<InteractionResponse, ()>(
+            Method::Post,
+            Routes::InteractionCallback(interaction_id, interaction_token),
+            response,
+            &attachments,
+        )
+        .await
+        .map(|_| ())
+    }
+
     pub async fn create_followup_message(
         &self,
         interaction_token: String,
         body: WebhookBody,
     ) -> crate::Result<Message> {
-        self.request::<WebhookBody, Message>(
+        let attachments = body.attachments.clone().unwrap_or_default();
+        self.request_multipart::<WebhookBody, Message>(
             Method::Post,
             Routes::ApplicationInteraction(self.application_id, interaction_token),
-            Some(body),
+            body,
+            &attachments,
+        )
+        .await
+        .and_then(required)
+    }
+
+    pub async fn edit_original_response(
+        &self,
+        interaction_token: String,
+        body: WebhookBody,
+    ) -> crate::Result<Message> {
+        let attachments = body.attachments.clone().unwrap_or_default();
+        self.request_multipart::<WebhookBody, Message>(
+            Method::Patch,
+            Routes::ApplicationInteractionOriginalMessage(self.application_id, interaction_token),
+            body,
+            &attachments,
+        )
+        .await
+        .and_then(required)
+    }
+
+    pub async fn edit_followup_message(
+        &self,
+        interaction_token: String,
+        message_id: Id<MessageMarker>,
+        body: WebhookBody,
+    ) -> crate::Result<Message> {
+        let attachments = body.attachments.clone().unwrap_or_default();
+        self.request_multipart::<WebhookBody, Message>(
+            Method::Patch,
+            Routes::ApplicationInteractionMessage(
+                self.application_id,
+                interaction_token,
+                message_id,
+            ),
+            body,
+            &attachments,
+        )
+        .await
+        .and_then(required)
+    }
+
+    pub async fn get_original_response(&self, interaction_token: String) -> crate::Result<Message> {
+        self.request::<(), Message>(
+            Method::Get,
+            Routes::ApplicationInteractionOriginalMessage(self.application_id, interaction_token),
+            None,
+        )
+        .await
+        .and_then(required)
+    }
+
+    pub async fn delete_original_response(&self, interaction_token: String) -> crate::Result<()> {
+        self.request::<(), ()>(
+            Method::Delete,
+            Routes::ApplicationInteractionOriginalMessage(self.application_id, interaction_token),
+            None,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    pub async fn get_followup_message(
+        &self,
+        interaction_token: String,
+        message_id: Id<MessageMarker>,
+    ) -> crate::Result<Message> {
+        self.request::<(), Message>(
+            Method::Get,
+            Routes::ApplicationInteractionMessage(
+                self.application_id,
+                interaction_token,
+                message_id,
+            ),
+            None,
+        )
+        .await
+        .and_then(required)
+    }
+
+    pub async fn delete_followup_message(
+        &self,
+        interaction_token: String,
+        message_id: Id<MessageMarker>,
+    ) -> crate::Result<()> {
+        self.request::<(), ()>(
+            Method::Delete,
+            Routes::ApplicationInteractionMessage(
+                self.application_id,
+                interaction_token,
+                message_id,
+            ),
+            None,
         )
         .await
-        .map(|x| x.unwrap())
+        .map(|_| ())
+    }
+}
+
+/**
+The lifetime of interaction tokens.
+**/
+pub const INTERACTION_TOKEN_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/**
+A handle to the original response and the followup messages of an interaction.
+It owns the client and the interaction token, so it can be moved into deferred work.
+The token is valid for 15 minutes after the interaction.
+**/
+#[derive(Clone)]
+pub struct Followup {
+    http: HttpClient,
+    token: String,
+    created_at: u64,
+}
+
+impl Followup {
+    pub fn new(http: HttpClient, token: String) -> Self {
+        Self {
+            http,
+            token,
+            created_at: now_millis(),
+        }
+    }
+
+    /**
+    Set the creation time of the token from the interaction id.
+    **/
+    pub fn with_interaction_id(mut self, interaction_id: Id<InteractionMarker>) -> Self {
+        self.created_at = (interaction_id.get() >> 22) + DISCORD_EPOCH;
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /**
+    The time elapsed since the interaction was created.
+    **/
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(now_millis().saturating_sub(self.created_at))
+    }
+
+    /**
+    Whether the token is expired.
+    **/
+    pub fn is_expired(&self) -> bool {
+        self.elapsed() >= INTERACTION_TOKEN_LIFETIME
+    }
+
+    /**
+    Wait for the duration with the timer of the client.
+    **/
+    pub(crate) async fn sleep(&self, duration: Duration) -> crate::Result<()> {
+        self.http.sleep(duration).await
+    }
+
+    /**
+    Build the message to edit the original response with.
+    Return it from the work of [`defer_with`](crate::application_command::ChatInputCommandContext::defer_with).
+    **/
+    pub fn edit<F>(&self, message: F) -> crate::Result<InteractionResponseBuilder>
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        let mut builder = InteractionResponseBuilder::default();
+        message(&mut builder);
+        builder.validate()?;
+        Ok(builder)
+    }
+
+    pub async fn get_original(&self) -> crate::Result<Message> {
+        self.http.get_original_response(self.token.clone()).await
+    }
+
+    pub async fn edit_original<F>(&self, message: F) -> crate::Result<Message>
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        self.http
+            .edit_original_response(self.token.clone(), webhook_body(message)?)
+            .await
+    }
+
+    pub async fn delete_original(&self) -> crate::Result<()> {
+        self.http.delete_original_response(self.token.clone()).await
+    }
+
+    pub async fn create_followup<F>(&self, message: F) -> crate::Result<Message>
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        self.http
+            .create_followup_message(self.token.clone(), webhook_body(message)?)
+            .await
+    }
+
+    pub async fn get_followup(&self, message_id: Id<MessageMarker>) -> crate::Result<Message> {
+        self.http
+            .get_followup_message(self.token.clone(), message_id)
+            .await
+    }
+
+    pub async fn edit_followup<F>(
+        &self,
+        message_id: Id<MessageMarker>,
+        message: F,
+    ) -> crate::Result<Message>
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        self.http
+            .edit_followup_message(self.token.clone(), message_id, webhook_body(message)?)
+            .await
+    }
+
+    pub async fn delete_followup(&self, message_id: Id<MessageMarker>) -> crate::Result<()> {
+        self.http
+            .delete_followup_message(self.token.clone(), message_id)
+            .await
     }
 }
+
+fn webhook_body<F>(message: F) -> crate::Result<WebhookBody>
+where
+    F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+{
+    let mut builder = InteractionResponseBuilder::default();
+    message(&mut builder);
+    Ok(WebhookBody::from(builder.try_build_data()?))
+}
@@ -0,0 +1,89 @@
+/**
+Generate methods of [`HttpClient`](super::HttpClient) from a table of endpoints.
+
+Each entry is a method signature and the HTTP method with the route.
+`body`, `query` and `reason` name the parameters sent as the JSON body, the query string
+and `X-Audit-Log-Reason` header. Methods without a return type ignore the response body.
+`map` converts the response body into the return type, and its argument type is deserialized.
+
+```ignore
+impl HttpClient {
+    rest_methods! {
+        fn modify_channel(
+            channel_id: Id<ChannelMarker>,
+            body: &ModifyChannel,
+            reason: Option<&str>,
+        ) -> Channel {
+            Patch Routes::Channel(channel_id), body: body, reason: reason
+        }
+
+        fn trigger_typing_indicator(channel_id: Id<ChannelMarker>) {
+            Post Routes::ChannelTyping(channel_id)
+        }
+    }
+}
+```
+**/
+macro_rules! rest_methods {
+    () => {};
+    (@query) => { None };
+    (@query $query:expr) => { Some(crate::http::query_string($query)?) };
+    (@body) => { None };
+    (@body $body:expr) => { Some(crate::http::json_body($body)?) };
+    (@reason) => { None };
+    (@reason $reason:expr) => { $reason };
+    (@map) => { std::convert::identity };
+    (@map $map:expr) => { $map };
+    (
+        $(#[$meta:meta])*
+        fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) {
+            $method:ident $route:expr
+            $(, body: $body:expr)?
+            $(, query: $query:expr)?
+            $(, reason: $reason:expr)?
+        }
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        pub async fn $name(&self, $($arg: $arg_ty),*) -> crate::Result<()> {
+            self.send::<serde::de::IgnoredAny>(
+                worker::Method::$method,
+                $route,
+                rest_methods!(@query $($query)?),
+                rest_methods!(@body $($body)?),
+                rest_methods!(@reason $($reason)?),
+            )
+            .await
+            .map(|_| ())
+        }
+
+        rest_methods!($($rest)*);
+    };
+    (
+        $(#[$meta:meta])*
+        fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty {
+            $method:ident $route:expr
+            $(, body: $body:expr)?
+            $(, query: $query:expr)?
+            $(, reason: $reason:expr)?
+            $(, map: $map:expr)?
+        }
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        pub async fn $name(&self, $($arg: $arg_ty),*) -> crate::Result<$ret> {
+            self.send(
+                worker::Method::$method,
+                $route,
+                rest_methods!(@query $($query)?),
+                rest_methods!(@body $($body)?),
+                rest_methods!(@reason $($reason)?),
+            )
+            .await
+            .and_then(crate::http::required)
+            .map(rest_methods!(@map $($map)?))
+        }
+
+        rest_methods!($($rest)*);
+    };
+}
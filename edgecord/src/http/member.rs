@@ -0,0 +1,117 @@
+use crate::http::{HttpClient, Routes};
+use crate::model::rest::member::{
+    CreateGuildBan, ListGuildMembers, ModifyCurrentMember, ModifyGuildMember, SearchGuildMembers,
+};
+use twilight_model::guild::member::MemberIntermediary;
+use twilight_model::guild::{Ban, Member};
+use twilight_model::id::marker::{GuildMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+
+/// The members returned by the API don't have the guild id.
+fn into_members(guild_id: Id<GuildMarker>, members: Vec<MemberIntermediary>) -> Vec<Member> {
+    members
+        .into_iter()
+        .map(|member| member.into_member(guild_id))
+        .collect()
+}
+
+impl HttpClient {
+    rest_methods! {
+        fn get_guild_member(guild_id: Id<GuildMarker>, user_id: Id<UserMarker>) -> Member {
+            Get Routes::GuildMember(guild_id, user_id),
+            map: |member: MemberIntermediary| member.into_member(guild_id)
+        }
+
+        /**
+        List the members of the guild. It requires `GUILD_MEMBERS` privileged intent.
+        **/
+        fn list_guild_members(
+            guild_id: Id<GuildMarker>,
+            query: &ListGuildMembers,
+        ) -> Vec<Member> {
+            Get Routes::GuildMembers(guild_id), query: query,
+            map: |members| into_members(guild_id, members)
+        }
+
+        fn search_guild_members(
+            guild_id: Id<GuildMarker>,
+            query: &SearchGuildMembers,
+        ) -> Vec<Member> {
+            Get Routes::GuildMembersSearch(guild_id), query: query,
+            map: |members| into_members(guild_id, members)
+        }
+
+        fn modify_guild_member(
+            guild_id: Id<GuildMarker>,
+            user_id: Id<UserMarker>,
+            body: &ModifyGuildMember,
+            reason: Option<&str>,
+        ) -> Member {
+            Patch Routes::GuildMember(guild_id, user_id), body: body, reason: reason,
+            map: |member: MemberIntermediary| member.into_member(guild_id)
+        }
+
+        fn modify_current_member(
+            guild_id: Id<GuildMarker>,
+            body: &ModifyCurrentMember,
+            reason: Option<&str>,
+        ) -> Member {
+            Patch Routes::GuildMemberMe(guild_id), body: body, reason: reason,
+            map: |member: MemberIntermediary| member.into_member(guild_id)
+        }
+
+        fn add_guild_member_role(
+            guild_id: Id<GuildMarker>,
+            user_id: Id<UserMarker>,
+            role_id: Id<RoleMarker>,
+            reason: Option<&str>,
+        ) {
+            Put Routes::GuildMemberRole(guild_id, user_id, role_id), reason: reason
+        }
+
+        fn remove_guild_member_role(
+            guild_id: Id<GuildMarker>,
+            user_id: Id<UserMarker>,
+            role_id: Id<RoleMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::GuildMemberRole(guild_id, user_id, role_id), reason: reason
+        }
+
+        /**
+        Kick the member from the guild.
+        **/
+        fn remove_guild_member(
+            guild_id: Id<GuildMarker>,
+            user_id: Id<UserMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::GuildMember(guild_id, user_id), reason: reason
+        }
+
+        fn get_guild_bans(guild_id: Id<GuildMarker>) -> Vec<Ban> {
+            Get Routes::GuildBans(guild_id)
+        }
+
+        fn get_guild_ban(guild_id: Id<GuildMarker>, user_id: Id<UserMarker>) -> Ban {
+            Get Routes::GuildBan(guild_id, user_id)
+        }
+
+        fn create_guild_ban(
+            guild_id: Id<GuildMarker>,
+            user_id: Id<UserMarker>,
+            body: &CreateGuildBan,
+            reason: Option<&str>,
+        ) {
+            Put Routes::GuildBan(guild_id, user_id), body: body, reason: reason
+        }
+
+        fn remove_guild_ban(
+            guild_id: Id<GuildMarker>,
+            user_id: Id<UserMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::GuildBan(guild_id, user_id), reason: reason
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::http::{required, HttpClient, Routes};
+use crate::model::rest::message::{
+    BulkDeleteMessages, CreateMessage, EditMessage, GetChannelMessages, GetReactions,
+};
+use crate::model::UrlEncodedEmoji;
+use twilight_model::channel::Message;
+use twilight_model::id::marker::{ChannelMarker, MessageMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_model::user::User;
+use worker::Method;
+
+impl HttpClient {
+    /**
+    Send a message to the channel. If the body has attachments, their files are uploaded together.
+    **/
+    pub async fn create_message(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        body: CreateMessage,
+    ) -> crate::Result<Message> {
+        let attachments = body.attachments.clone().unwrap_or_default();
+        self.request_multipart::<CreateMessage, Message>(
+            Method::Post,
+            Routes::ChannelMessages(channel_id),
+            body,
+            &attachments,
+        )
+        .await
+        .and_then(required)
+    }
+
+    /**
+    Edit a message sent by the bot. If the body has attachments, their files are uploaded together.
+    **/
+    pub async fn edit_message(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+        body: EditMessage,
+    ) -> crate::Result<Message> {
+        let attachments = body.attachments.clone().unwrap_or_default();
+        self.request_multipart::<EditMessage, Message>(
+            Method::Patch,
+            Routes::ChannelMessage(channel_id, message_id),
+            body,
+            &attachments,
+        )
+        .await
+        .and_then(required)
+    }
+
+    rest_methods! {
+        fn get_channel_messages(
+            channel_id: Id<ChannelMarker>,
+            query: &GetChannelMessages,
+        ) -> Vec<Message> {
+            Get Routes::ChannelMessages(channel_id), query: query
+        }
+
+        fn get_channel_message(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+        ) -> Message {
+            Get Routes::ChannelMessage(channel_id, message_id)
+        }
+
+        /**
+        Publish a message in an announcement channel to the following channels.
+        **/
+        fn crosspost_message(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+        ) -> Message {
+            Post Routes::ChannelMessageCrosspost(channel_id, message_id)
+        }
+
+        fn delete_message(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::ChannelMessage(channel_id, message_id), reason: reason
+        }
+
+        fn bulk_delete_messages(
+            channel_id: Id<ChannelMarker>,
+            body: &BulkDeleteMessages,
+            reason: Option<&str>,
+        ) {
+            Post Routes::ChannelMessagesBulkDelete(channel_id), body: body, reason: reason
+        }
+
+        fn get_pinned_messages(channel_id: Id<ChannelMarker>) -> Vec<Message> {
+            Get Routes::ChannelPins(channel_id)
+        }
+
+        fn pin_message(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            reason: Option<&str>,
+        ) {
+            Put Routes::ChannelPinMessage(channel_id, message_id), reason: reason
+        }
+
+        fn unpin_message(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::ChannelPinMessage(channel_id, message_id), reason: reason
+        }
+
+        fn create_reaction(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            emoji: UrlEncodedEmoji,
+        ) {
+            Put Routes::ChannelMessageReactionEmojiMe(channel_id, message_id, emoji)
+        }
+
+        fn delete_own_reaction(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            emoji: UrlEncodedEmoji,
+        ) {
+            Delete Routes::ChannelMessageReactionEmojiMe(channel_id, message_id, emoji)
+        }
+
+        fn delete_user_reaction(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            emoji: UrlEncodedEmoji,
+            user_id: Id<UserMarker>,
+        ) {
+            Delete Routes::ChannelMessageReactionEmojiUser(channel_id, message_id, emoji, user_id)
+        }
+
+        /**
+        Get the users who reacted with the emoji.
+        **/
+        fn get_reactions(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            emoji: UrlEncodedEmoji,
+            query: &GetReactions,
+        ) -> Vec<User> {
+            Get Routes::ChannelMessageReactionEmoji(channel_id, message_id, emoji), query: query
+        }
+
+        fn delete_all_reactions(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+        ) {
+            Delete Routes::ChannelMessageReactions(channel_id, message_id)
+        }
+
+        fn delete_all_reactions_for_emoji(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            emoji: UrlEncodedEmoji,
+        ) {
+            Delete Routes::ChannelMessageReactionEmoji(channel_id, message_id, emoji)
+        }
+    }
+}
@@ -1,51 +1,95 @@
+#[macro_use]
+mod macros;
+
+pub mod application_command;
 mod bucket;
+pub mod channel;
+mod config;
 pub mod guild;
 pub mod interaction;
+pub mod member;
+pub mod message;
+mod multipart;
+pub mod role;
 mod route;
+pub mod thread;
+pub mod transport;
 
-use cfg_if::cfg_if;
+pub use application_command::CommandScope;
+pub use bucket::{BucketState, DefaultRateLimitBucket, KvRateLimitBucket, RateLimitBucket};
+pub use config::{HttpClientBuilder, HttpConfig};
+pub use interaction::{Followup, INTERACTION_TOKEN_LIFETIME};
+pub use multipart::Form;
 pub use route::Routes;
+pub use transport::{Transport, TransportRequest, TransportResponse};
 
+use crate::error::ApiErrorCode;
 use crate::Error;
+pub(crate) use bucket::now_millis;
+use bucket::{bucket_key, parse_headers, GLOBAL_BUCKET_KEY};
+use futures::future::Either;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::rc::Rc;
+use std::time::Duration;
+use twilight_model::http::attachment::Attachment;
 use twilight_model::id::marker::ApplicationMarker;
 use twilight_model::id::Id;
 use worker::Method;
 
-cfg_if! {
-    if #[cfg(all(not(target_arch = "wasm32"), feature = "local"))] {
-        use std::str::FromStr;
-    } else {
-        use worker::wasm_bindgen::JsValue;
-        use edgelord::http::RequestBuilder;
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct HttpClient {
     token: String,
     application_id: Id<ApplicationMarker>,
-    ua: String,
-    #[cfg(not(target_arch = "wasm32"))]
-    _http: reqwest::Client,
+    config: Rc<HttpConfig>,
+    bucket: Rc<dyn RateLimitBucket>,
+    transport: Rc<dyn Transport>,
 }
 
-#[allow(dead_code)]
-const BASE_URL: &str = "https://discord.com/api/v10";
+const MAX_RETRIES: u32 = 3;
 
 impl HttpClient {
     pub fn new(token: &str, application_id: Id<ApplicationMarker>) -> Self {
-        Self {
-            token: format!("Bot {token}"),
-            application_id,
-            ua: "Discord Bot (https://github.com/sizumita/edgelord 0.0.1)".to_string(),
-            #[cfg(not(target_arch = "wasm32"))]
-            _http: reqwest::Client::new(),
-        }
+        Self::builder(token, application_id).build()
+    }
+
+    /**
+    Returns [`HttpClientBuilder`] to configure the base URL, the API version, the headers and the timeout.
+    **/
+    pub fn builder(token: &str, application_id: Id<ApplicationMarker>) -> HttpClientBuilder {
+        HttpClientBuilder::new(token, application_id)
+    }
+
+    pub fn config(&self) -> &HttpConfig {
+        &self.config
+    }
+
+    /**
+    Use the rate limit bucket to share the rate limit state.
+    **/
+    pub fn with_rate_limit_bucket(mut self, bucket: Rc<dyn RateLimitBucket>) -> Self {
+        self.bucket = bucket;
+        self
+    }
+
+    /**
+    Send the requests with the transport. See [`transport`] module.
+    **/
+    pub fn with_transport(mut self, transport: Rc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /**
+    Wait for the duration with the timer of the transport.
+    **/
+    pub(crate) async fn sleep(&self, duration: Duration) -> crate::Result<()> {
+        self.transport.sleep(duration).await
     }
 
-    #[cfg(target_arch = "wasm32")]
     pub async fn request<B, T>(
         &self,
         method: Method,
@@ -56,64 +100,413 @@ impl HttpClient {
         T: DeserializeOwned,
         B: Serialize,
     {
-        let mut response = RequestBuilder::new(&*format!("{}{}", BASE_URL, route))
-            .method(method)
-            .header("Content-Type", "application/json")
-            .header("Authorization", &*self.token)
-            .header("User-Agent", &*self.ua)
-            .body(body.and_then(|b| Some(JsValue::from(&*serde_json::to_string(&b).unwrap()))))
-            .send()
-            .await
-            .unwrap();
-
-        match response.status_code() {
-            204 => Ok(None),
-            i if i < 399 => Ok(Some(response.json::<T>().await.unwrap())),
-            403 => Err(Error::Forbidden),
-            404 => Err(Error::NotFound),
-            // TODO: add errors
-            _ => Err(Error::HttpError(response.text().await.unwrap())),
-        }
+        self.request_with_reason(method, route, body, None).await
     }
 
-    #[cfg(all(not(target_arch = "wasm32"), feature = "local"))]
-    pub async fn request<B, T>(
+    /**
+    Send a JSON request with `X-Audit-Log-Reason` header.
+    The reason is shown in the audit log of the guild if the endpoint supports it.
+    **/
+    pub async fn request_with_reason<B, T>(
         &self,
         method: Method,
         route: Routes,
         body: Option<B>,
+        reason: Option<&str>,
+    ) -> crate::Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        B: Serialize,
+    {
+        let body = body.map(json_body).transpose()?;
+        self.send(method, route, None, body, reason).await
+    }
+
+    /**
+    Send a request with the query string serialized from `query`. The fields of `None` are omitted.
+    **/
+    pub async fn request_with_query<Q, T>(
+        &self,
+        method: Method,
+        route: Routes,
+        query: &Q,
+    ) -> crate::Result<Option<T>>
+    where
+        T: DeserializeOwned,
+        Q: Serialize,
+    {
+        let query = query_string(query)?;
+        self.send(method, route, Some(query), None, None).await
+    }
+
+    /**
+    Send a multipart/form-data request with `payload_json` and files of the attachments.
+    If there are no attachments, it sends json request.
+    **/
+    pub async fn request_multipart<B, T>(
+        &self,
+        method: Method,
+        route: Routes,
+        body: B,
+        attachments: &[Attachment],
     ) -> crate::Result<Option<T>>
     where
         T: DeserializeOwned,
         B: Serialize,
     {
-        let response = self
-            ._http
-            .request(
-                reqwest::Method::from_str(&*method.to_string()).unwrap(),
-                &*format!("{}{}", BASE_URL, route),
-            )
-            .header("Content-Type", "application/json")
-            .header("Authorization", &*self.token)
-            .header("User-Agent", &*self.ua)
-            .body(
-                body.map(|x| serde_json::to_string(&x).unwrap())
-                    .unwrap_or_default(),
-            )
-            .send()
-            .await
-            .unwrap();
-
-        if response.status().clone().is_success() {
-            if response.status().as_u16() == 204 {
-                return Ok(None);
+        if attachments.is_empty() {
+            return self.request(method, route, Some(body)).await;
+        }
+        let form = Form::with_attachments(&body, attachments);
+        let body = (form.content_type(), form.build());
+        self.send(method, route, None, Some(body), None).await
+    }
+
+    /**
+    Send the request with waiting for the rate limit.
+    If the response is 429, it waits `retry_after` and retries up to [`MAX_RETRIES`] times.
+    **/
+    async fn send<T>(
+        &self,
+        method: Method,
+        route: Routes,
+        query: Option<String>,
+        body: Option<(String, Vec<u8>)>,
+        reason: Option<&str>,
+    ) -> crate::Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut path = route.to_string();
+        let key = bucket_key(&method, &path);
+        if let Some(query) = query.filter(|x| !x.is_empty()) {
+            path = format!("{}?{}", path, query);
+        }
+        let mut retries = 0;
+        let response = loop {
+            self.wait_rate_limit(&key).await?;
+            let response = self.fetch(&method, &path, &body, reason).await?;
+            let now = now_millis();
+            if let Some(state) = parse_headers(&response.headers, now) {
+                self.bucket.put(&key, state).await;
+            }
+            if response.status != 429 || retries >= MAX_RETRIES {
+                break response;
             }
-            return Ok(Some(response.json::<T>().await.unwrap()));
+            let limited = serde_json::from_str::<RateLimited>(&response.body).unwrap_or_default();
+            let retry_after = (limited.retry_after * 1000.0).ceil() as u64;
+            if limited.global
+                || response.headers.get("x-ratelimit-global").map(|x| &**x) == Some("true")
+            {
+                self.bucket
+                    .put(
+                        GLOBAL_BUCKET_KEY,
+                        BucketState {
+                            remaining: 0,
+                            reset_at: now + retry_after,
+                        },
+                    )
+                    .await;
+            }
+            self.transport
+                .sleep(Duration::from_millis(retry_after))
+                .await?;
+            retries += 1;
+        };
+
+        match response.status {
+            204 => Ok(None),
+            i if i < 300 => serde_json::from_str::<T>(&response.body)
+                .map(Some)
+                .map_err(|e| Error::Decode(e.to_string())),
+            _ => Err(parse_error(response)),
         }
-        match response.status() {
-            reqwest::StatusCode::FORBIDDEN => Err(Error::Forbidden),
-            reqwest::StatusCode::NOT_FOUND => Err(Error::NotFound),
-            _ => Err(Error::HttpError(response.text().await.unwrap_or_default())),
+    }
+
+    async fn fetch(
+        &self,
+        method: &Method,
+        path: &str,
+        body: &Option<(String, Vec<u8>)>,
+        reason: Option<&str>,
+    ) -> crate::Result<RawResponse> {
+        let mut headers = self.config.headers.clone();
+        headers.push(("Authorization".to_string(), self.token.clone()));
+        headers.push(("User-Agent".to_string(), self.config.user_agent()));
+        if let Some((content_type, _)) = body {
+            headers.push(("Content-Type".to_string(), content_type.clone()));
+        }
+        if let Some(reason) = reason {
+            headers.push((
+                "X-Audit-Log-Reason".to_string(),
+                utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string(),
+            ));
+        }
+        let send = self.transport.send(TransportRequest {
+            method: method.clone(),
+            url: format!("{}{}", self.config.api_url(), path),
+            headers,
+            body: body.as_ref().map(|(_, body)| body.clone()),
+        });
+        let response = match self.config.timeout {
+            Some(duration) => timeout(send, self.transport.sleep(duration), duration).await??,
+            None => send.await?,
+        };
+        Ok(RawResponse {
+            status: response.status,
+            headers: response.headers,
+            body: String::from_utf8_lossy(&response.body).into_owned(),
+        })
+    }
+
+    async fn wait_rate_limit(&self, key: &str) -> crate::Result<()> {
+        for key in [GLOBAL_BUCKET_KEY, key] {
+            if let Some(wait) = self
+                .bucket
+                .get(key)
+                .await
+                .and_then(|state| state.wait_millis(now_millis()))
+            {
+                self.transport.sleep(Duration::from_millis(wait)).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for HttpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("application_id", &self.application_id)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+fn query_string<Q: Serialize>(query: &Q) -> crate::Result<String> {
+    serde_urlencoded::to_string(query)
+        .map_err(|e| Error::Transport(format!("failed to serialize the query: {}", e)))
+}
+
+fn json_body<B: Serialize>(body: B) -> crate::Result<(String, Vec<u8>)> {
+    serde_json::to_vec(&body)
+        .map(|body| ("application/json".to_string(), body))
+        .map_err(|e| Error::Transport(format!("failed to serialize the body: {}", e)))
+}
+
+/**
+Wait for the future until the delay completes.
+The delay is polled first, so an error of the timer is returned without waiting for the future.
+**/
+async fn timeout<F, D>(future: F, delay: D, duration: Duration) -> crate::Result<F::Output>
+where
+    F: Future,
+    D: Future<Output = crate::Result<()>>,
+{
+    futures::pin_mut!(future);
+    futures::pin_mut!(delay);
+    match futures::future::select(delay, future).await {
+        Either::Left((result, _)) => result.and(Err(Error::Timeout(duration))),
+        Either::Right((output, _)) => Ok(output),
+    }
+}
+
+/**
+Unwrap the response body which must not be empty.
+**/
+pub(crate) fn required<T>(value: Option<T>) -> crate::Result<T> {
+    value.ok_or_else(|| Error::Decode("the response body is empty".to_string()))
+}
+
+/**
+Parse the error response of Discord API.
+**/
+fn parse_error(response: RawResponse) -> Error {
+    match response.status {
+        401 => Error::Unauthorized,
+        429 => {
+            let limited = serde_json::from_str::<RateLimited>(&response.body).unwrap_or_default();
+            Error::RateLimited {
+                retry_after: limited.retry_after,
+                global: limited.global,
+            }
         }
+        status => match serde_json::from_str::<ApiErrorBody>(&response.body) {
+            Ok(body) => Error::Api {
+                status,
+                code: ApiErrorCode::from(body.code),
+                message: body.message,
+                errors: body.errors,
+            },
+            Err(_) => Error::Api {
+                status,
+                code: ApiErrorCode::General,
+                message: response.body,
+                errors: None,
+            },
+        },
+    }
+}
+
+struct RawResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    code: u64,
+    message: String,
+    #[serde(default)]
+    errors: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RateLimited {
+    #[serde(default)]
+    retry_after: f64,
+    #[serde(default)]
+    global: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_error(RawResponse {
+            status: 404,
+            headers: HashMap::new(),
+            body: r#"{"code": 10008, "message": "Unknown Message"}"#.to_string(),
+        });
+        assert_eq!(error.api_code(), Some(ApiErrorCode::UnknownMessage));
+
+        let error = parse_error(RawResponse {
+            status: 400,
+            headers: HashMap::new(),
+            body: r#"{"code": 50035, "message": "Invalid Form Body", "errors": {"content": {"_errors": []}}}"#.to_string(),
+        });
+        match error {
+            Error::Api {
+                status,
+                code,
+                errors,
+                ..
+            } => {
+                assert_eq!(status, 400);
+                assert_eq!(code, ApiErrorCode::InvalidFormBody);
+                assert!(errors.unwrap().get("content").is_some());
+            }
+            _ => panic!("expected api error"),
+        }
+
+        assert!(matches!(
+            parse_error(RawResponse {
+                status: 401,
+                headers: HashMap::new(),
+                body: String::new(),
+            }),
+            Error::Unauthorized
+        ));
+    }
+
+    #[test]
+    fn test_transport() {
+        let mock = Rc::new(transport::MockTransport::new());
+        mock.respond(200, r#"{"id": "1"}"#);
+        let client = HttpClient::new("token", Id::new(1)).with_transport(mock.clone());
+        let value = futures::executor::block_on(client.request::<(), serde_json::Value>(
+            Method::Get,
+            Routes::Guild(Id::new(2)),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(value.unwrap()["id"], "1");
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].url, "https://discord.com/api/v10/guilds/2");
+        assert_eq!(requests[0].header("authorization"), Some("Bot token"));
+    }
+
+    #[test]
+    fn test_invalid_query() {
+        let mock = Rc::new(transport::MockTransport::new());
+        let client = HttpClient::new("token", Id::new(1)).with_transport(mock.clone());
+        let result = futures::executor::block_on(client.request_with_query::<_, ()>(
+            Method::Get,
+            Routes::Guild(Id::new(2)),
+            &[("ids", [1, 2])],
+        ));
+        assert!(matches!(result, Err(Error::Transport(_))));
+        assert!(mock.requests().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_body() {
+        let mock = Rc::new(transport::MockTransport::new());
+        let client = HttpClient::new("token", Id::new(1)).with_transport(mock.clone());
+        let result = futures::executor::block_on(client.request::<_, ()>(
+            Method::Post,
+            Routes::Guild(Id::new(2)),
+            Some(HashMap::from([((1, 2), 3)])),
+        ));
+        assert!(matches!(result, Err(Error::Transport(_))));
+        assert!(mock.requests().is_empty());
+    }
+
+    #[test]
+    fn test_rest_methods() {
+        use crate::model::rest::member::ModifyGuildMember;
+        use crate::model::rest::message::GetChannelMessages;
+
+        let mock = Rc::new(transport::MockTransport::new());
+        let client = HttpClient::new("token", Id::new(1)).with_transport(mock.clone());
+        mock.respond(200, "[]");
+        let messages = futures::executor::block_on(client.get_channel_messages(
+            Id::new(2),
+            &GetChannelMessages {
+                limit: Some(10),
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+        assert!(messages.is_empty());
+        futures::executor::block_on(client.delete_message(
+            Id::new(2),
+            Id::new(3),
+            Some("spam & ads"),
+        ))
+        .unwrap();
+
+        mock.respond(
+            200,
+            r#"{"deaf": false, "joined_at": "2022-01-01T00:00:00.000000+00:00", "mute": false, "nick": null,
+            "roles": [], "user": {"id": "5", "username": "a", "discriminator": "0001", "avatar": null}}"#,
+        );
+        let member = futures::executor::block_on(client.modify_guild_member(
+            Id::new(4),
+            Id::new(5),
+            &ModifyGuildMember {
+                nick: Some(None),
+                ..Default::default()
+            },
+            None,
+        ))
+        .unwrap();
+        assert_eq!(member.guild_id, Id::new(4));
+
+        let requests = mock.requests();
+        assert_eq!(
+            requests[0].url,
+            "https://discord.com/api/v10/channels/2/messages?limit=10"
+        );
+        assert_eq!(
+            requests[1].header("x-audit-log-reason"),
+            Some("spam%20%26%20ads")
+        );
+        assert_eq!(requests[2].body.as_deref(), Some(&br#"{"nick":null}"#[..]));
+        assert_eq!(requests[2].header("x-audit-log-reason"), None);
     }
 }
@@ -0,0 +1,148 @@
+use serde::Serialize;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::{BuildHasher, Hash, Hasher};
+use twilight_model::http::attachment::Attachment;
+
+const BOUNDARY_PREFIX: &str = "edgecord-multipart-boundary-";
+
+/**
+A multipart/form-data body.
+Discord accepts the json payload as `payload_json` part and the files as `files[n]` parts.
+
+The boundary is random for each form, and is regenerated if any part contains it.
+**/
+#[derive(Debug, Clone)]
+pub struct Form {
+    seed: u64,
+    parts: Vec<Vec<u8>>,
+}
+
+impl Form {
+    pub fn new() -> Self {
+        Self {
+            seed: RandomState::new().build_hasher().finish(),
+            parts: Vec::new(),
+        }
+    }
+
+    /**
+    Create a form for the payload and the attachments.
+    The file of the attachment is sent as `files[{attachment.id}]` part.
+    **/
+    pub fn with_attachments<B: Serialize>(payload: &B, attachments: &[Attachment]) -> Self {
+        let mut form = Self::new();
+        form.json_part("payload_json", &serde_json::to_vec(payload).unwrap());
+        for attachment in attachments {
+            form.file_part(
+                &format!("files[{}]", attachment.id),
+                &attachment.filename,
+                &attachment.file,
+            );
+        }
+        form
+    }
+
+    pub fn json_part(&mut self, name: &str, value: &[u8]) -> &mut Self {
+        let mut part = format!(
+            "Content-Disposition: form-data; name=\"{}\"\r\nContent-Type: application/json\r\n\r\n",
+            name
+        )
+        .into_bytes();
+        part.extend_from_slice(value);
+        self.parts.push(part);
+        self
+    }
+
+    pub fn file_part(&mut self, name: &str, filename: &str, file: &[u8]) -> &mut Self {
+        let mut part = format!(
+            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+            name,
+            filename.replace('"', "\\\"")
+        )
+        .into_bytes();
+        part.extend_from_slice(file);
+        self.parts.push(part);
+        self
+    }
+
+    /**
+    The boundary that no part contains.
+    The candidates have the same length, so a collision with one does not imply a collision with the next.
+    **/
+    pub fn boundary(&self) -> String {
+        (0u64..)
+            .map(|attempt| {
+                let mut hasher = DefaultHasher::new();
+                (self.seed, attempt).hash(&mut hasher);
+                format!("{}{:016x}", BOUNDARY_PREFIX, hasher.finish())
+            })
+            .find(|boundary| {
+                !self.parts.iter().any(|part| {
+                    part.windows(boundary.len())
+                        .any(|w| w == boundary.as_bytes())
+                })
+            })
+            .unwrap()
+    }
+
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary())
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let boundary = self.boundary();
+        let mut body = Vec::new();
+        for part in &self.parts {
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            body.extend_from_slice(part);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+        body
+    }
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_with_attachments() {
+        let attachment = Attachment::from_bytes("a.txt".to_string(), b"hello".to_vec(), 0);
+        let form = Form::with_attachments(
+            &serde_json::json!({"attachments": [&attachment]}),
+            &[attachment],
+        );
+        let body = String::from_utf8(form.build()).unwrap();
+        assert_eq!(
+            body,
+            format!(
+                "--{b}\r\nContent-Disposition: form-data; name=\"payload_json\"\r\nContent-Type: application/json\r\n\r\n\
+                {{\"attachments\":[{{\"filename\":\"a.txt\",\"id\":0}}]}}\r\n\
+                --{b}\r\nContent-Disposition: form-data; name=\"files[0]\"; filename=\"a.txt\"\r\nContent-Type: application/octet-stream\r\n\r\n\
+                hello\r\n--{b}--\r\n",
+                b = form.boundary()
+            )
+        );
+    }
+
+    #[test]
+    fn test_boundary() {
+        assert_ne!(Form::new().boundary(), Form::new().boundary());
+
+        let mut form = Form::new();
+        let boundary = form.boundary();
+        form.file_part("files[0]", "a.txt", boundary.as_bytes());
+        assert_ne!(form.boundary(), boundary);
+        assert_eq!(
+            form.content_type(),
+            format!("multipart/form-data; boundary={}", form.boundary())
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::http::{HttpClient, Routes};
+use crate::model::rest::role::{ModifyGuildRole, ModifyGuildRolePosition};
+use twilight_model::guild::Role;
+use twilight_model::id::marker::{GuildMarker, RoleMarker};
+use twilight_model::id::Id;
+
+impl HttpClient {
+    rest_methods! {
+        fn get_guild_roles(guild_id: Id<GuildMarker>) -> Vec<Role> {
+            Get Routes::GuildRoles(guild_id)
+        }
+
+        fn create_guild_role(
+            guild_id: Id<GuildMarker>,
+            body: &ModifyGuildRole,
+            reason: Option<&str>,
+        ) -> Role {
+            Post Routes::GuildRoles(guild_id), body: body, reason: reason
+        }
+
+        /**
+        Move the roles. Returns all roles of the guild.
+        **/
+        fn modify_guild_role_positions(
+            guild_id: Id<GuildMarker>,
+            body: &[ModifyGuildRolePosition],
+            reason: Option<&str>,
+        ) -> Vec<Role> {
+            Patch Routes::GuildRoles(guild_id), body: body, reason: reason
+        }
+
+        fn modify_guild_role(
+            guild_id: Id<GuildMarker>,
+            role_id: Id<RoleMarker>,
+            body: &ModifyGuildRole,
+            reason: Option<&str>,
+        ) -> Role {
+            Patch Routes::GuildRole(guild_id, role_id), body: body, reason: reason
+        }
+
+        fn delete_guild_role(
+            guild_id: Id<GuildMarker>,
+            role_id: Id<RoleMarker>,
+            reason: Option<&str>,
+        ) {
+            Delete Routes::GuildRole(guild_id, role_id), reason: reason
+        }
+    }
+}
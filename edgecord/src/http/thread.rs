@@ -0,0 +1,88 @@
+use crate::http::{HttpClient, Routes};
+use crate::model::rest::thread::{
+    ListArchivedThreads, ListJoinedArchivedThreads, StartThreadFromMessage,
+    StartThreadWithoutMessage,
+};
+use twilight_model::channel::thread::{ThreadMember, ThreadsListing};
+use twilight_model::channel::Channel;
+use twilight_model::id::marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker};
+use twilight_model::id::Id;
+
+impl HttpClient {
+    rest_methods! {
+        fn start_thread_from_message(
+            channel_id: Id<ChannelMarker>,
+            message_id: Id<MessageMarker>,
+            body: &StartThreadFromMessage,
+            reason: Option<&str>,
+        ) -> Channel {
+            Post Routes::ChannelMessageThreads(channel_id, message_id), body: body, reason: reason
+        }
+
+        fn start_thread_without_message(
+            channel_id: Id<ChannelMarker>,
+            body: &StartThreadWithoutMessage,
+            reason: Option<&str>,
+        ) -> Channel {
+            Post Routes::ChannelThreads(channel_id), body: body, reason: reason
+        }
+
+        fn join_thread(channel_id: Id<ChannelMarker>) {
+            Put Routes::ChannelThreadMemberMe(channel_id)
+        }
+
+        fn add_thread_member(channel_id: Id<ChannelMarker>, user_id: Id<UserMarker>) {
+            Put Routes::ChannelThreadMember(channel_id, user_id)
+        }
+
+        fn leave_thread(channel_id: Id<ChannelMarker>) {
+            Delete Routes::ChannelThreadMemberMe(channel_id)
+        }
+
+        fn remove_thread_member(channel_id: Id<ChannelMarker>, user_id: Id<UserMarker>) {
+            Delete Routes::ChannelThreadMember(channel_id, user_id)
+        }
+
+        fn get_thread_member(
+            channel_id: Id<ChannelMarker>,
+            user_id: Id<UserMarker>,
+        ) -> ThreadMember {
+            Get Routes::ChannelThreadMember(channel_id, user_id)
+        }
+
+        fn list_thread_members(channel_id: Id<ChannelMarker>) -> Vec<ThreadMember> {
+            Get Routes::ChannelThreadMembers(channel_id)
+        }
+
+        fn list_public_archived_threads(
+            channel_id: Id<ChannelMarker>,
+            query: &ListArchivedThreads,
+        ) -> ThreadsListing {
+            Get Routes::ChannelThreadsArchivedPublic(channel_id), query: query
+        }
+
+        fn list_private_archived_threads(
+            channel_id: Id<ChannelMarker>,
+            query: &ListArchivedThreads,
+        ) -> ThreadsListing {
+            Get Routes::ChannelThreadsArchivedPrivate(channel_id), query: query
+        }
+
+        /**
+        List the private archived threads that the bot has joined.
+        **/
+        fn list_joined_private_archived_threads(
+            channel_id: Id<ChannelMarker>,
+            query: &ListJoinedArchivedThreads,
+        ) -> ThreadsListing {
+            Get Routes::ChannelUserMeThreadArchivedPrivate(channel_id), query: query
+        }
+
+        /**
+        List the active threads of the guild, including private threads.
+        **/
+        fn list_active_guild_threads(guild_id: Id<GuildMarker>) -> ThreadsListing {
+            Get Routes::GuildThreadsActive(guild_id)
+        }
+    }
+}
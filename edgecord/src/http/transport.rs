@@ -0,0 +1,478 @@
+/*!
+Transports that send the requests of [`HttpClient`](super::HttpClient).
+
+The default transport is [`FetchTransport`] on workers and [`ReqwestTransport`] on native targets with `local` feature.
+Implement [`Transport`] to send the requests through a proxy or another runtime.
+*/
+use super::bucket::sleep;
+use crate::Error;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::time::Duration;
+use worker::Method;
+
+/**
+An HTTP request to Discord API.
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransportRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl TransportRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(x, _)| x.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /**
+    Decode the JSON body.
+    **/
+    pub fn json<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_slice(self.body.as_ref()?).ok()
+    }
+}
+
+/**
+An HTTP response. The header names are lowercase.
+**/
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TransportResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    pub fn new(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: HashMap::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+pub trait Transport {
+    async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse>;
+
+    /**
+    Wait for the duration. It is used to wait for the rate limits.
+    The default is `setTimeout` on workers and tokio with `local` feature.
+    Otherwise it returns an error, so implement it with the timer of your runtime.
+    **/
+    async fn sleep(&self, duration: Duration) -> crate::Result<()> {
+        sleep(duration).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<T> Transport for Rc<T>
+where
+    T: Transport + ?Sized,
+{
+    async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+        (**self).send(request).await
+    }
+
+    async fn sleep(&self, duration: Duration) -> crate::Result<()> {
+        (**self).sleep(duration).await
+    }
+}
+
+/**
+The default transport of the target.
+**/
+pub(crate) fn default_transport() -> Rc<dyn Transport> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_arch = "wasm32")] {
+            Rc::new(FetchTransport)
+        } else if #[cfg(feature = "local")] {
+            Rc::new(ReqwestTransport::new())
+        } else {
+            Rc::new(NoTransport)
+        }
+    }
+}
+
+/**
+A transport with the fetch API of workers.
+**/
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Default)]
+pub struct FetchTransport;
+
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+impl Transport for FetchTransport {
+    async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+        use edgelord::http::RequestBuilder;
+        use worker::js_sys::Uint8Array;
+        use worker::wasm_bindgen::JsValue;
+
+        let mut builder = RequestBuilder::new(&request.url);
+        builder.method(request.method);
+        for (name, value) in &request.headers {
+            builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder.body(Some(JsValue::from(Uint8Array::from(&body[..]))));
+        }
+        let mut response = builder
+            .send()
+            .await
+            .map_err(|e| Error::Transport(e.to_string()))?;
+        Ok(TransportResponse {
+            status: response.status_code(),
+            headers: response.headers().entries().collect(),
+            body: response
+                .bytes()
+                .await
+                .map_err(|e| Error::Transport(e.to_string()))?,
+        })
+    }
+}
+
+/**
+A transport with reqwest for native targets.
+**/
+#[cfg(all(not(target_arch = "wasm32"), feature = "local"))]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "local"))]
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "local"))]
+#[async_trait(?Send)]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+        use std::str::FromStr;
+
+        let method = reqwest::Method::from_str(request.method.as_ref())
+            .map_err(|e| Error::Transport(e.to_string()))?;
+        let mut builder = self.client.request(method, &*request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| Error::Transport(e.to_string()))?;
+        Ok(TransportResponse {
+            status: response.status().as_u16(),
+            headers: response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((
+                        name.as_str().to_lowercase(),
+                        value.to_str().ok()?.to_string(),
+                    ))
+                })
+                .collect(),
+            body: response
+                .bytes()
+                .await
+                .map(|x| x.to_vec())
+                .map_err(|e| Error::Transport(e.to_string()))?,
+        })
+    }
+}
+
+/**
+The default transport on native targets without `local` feature. All requests fail.
+**/
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "local")))]
+#[derive(Debug, Clone, Default)]
+struct NoTransport;
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "local")))]
+#[async_trait(?Send)]
+impl Transport for NoTransport {
+    async fn send(&self, _request: TransportRequest) -> crate::Result<TransportResponse> {
+        Err(Error::Transport(
+            "no transport is available, enable `local` feature or set a transport".to_string(),
+        ))
+    }
+}
+
+/**
+A request and its response recorded by [`RecordingTransport`].
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+    pub request: TransportRequest,
+    pub response: TransportResponse,
+}
+
+/**
+A transport that records the requests and the responses of the inner transport.
+The exchanges can be replayed by [`MockTransport::replay`].
+**/
+pub struct RecordingTransport<T> {
+    inner: T,
+    exchanges: RefCell<Vec<Exchange>>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            exchanges: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn exchanges(&self) -> Vec<Exchange> {
+        self.exchanges.borrow().clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Transport> Transport for RecordingTransport<T> {
+    async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+        let response = self.inner.send(request.clone()).await?;
+        self.exchanges.borrow_mut().push(Exchange {
+            request,
+            response: response.clone(),
+        });
+        Ok(response)
+    }
+
+    async fn sleep(&self, duration: Duration) -> crate::Result<()> {
+        self.inner.sleep(duration).await
+    }
+}
+
+/**
+A mock of Discord API. It records the requests and responds with the registered responses.
+
+The responses for the method and the url are used first, and then the queued responses in order.
+If there are no responses, it responds 204 No Content.
+**/
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    requests: RefCell<Vec<TransportRequest>>,
+    routes: RefCell<Vec<(Method, String, VecDeque<TransportResponse>)>>,
+    responses: RefCell<VecDeque<TransportResponse>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Replay the recorded exchanges. Each response is used once for the method and the url.
+    **/
+    pub fn replay(exchanges: Vec<Exchange>) -> Self {
+        let mock = Self::new();
+        for exchange in exchanges {
+            mock.on(
+                exchange.request.method,
+                &exchange.request.url,
+                exchange.response,
+            );
+        }
+        mock
+    }
+
+    /**
+    Queue a response for any request.
+    **/
+    pub fn respond(&self, status: u16, body: &str) -> &Self {
+        self.responses
+            .borrow_mut()
+            .push_back(TransportResponse::new(status, body));
+        self
+    }
+
+    pub fn respond_json<T: serde::Serialize>(&self, status: u16, body: &T) -> &Self {
+        self.respond(status, &serde_json::to_string(body).unwrap())
+    }
+
+    /**
+    Queue a response for the method and the url.
+    **/
+    pub fn on(&self, method: Method, url: &str, response: TransportResponse) -> &Self {
+        let mut routes = self.routes.borrow_mut();
+        match routes.iter_mut().find(|(m, u, _)| *m == method && u == url) {
+            Some((_, _, responses)) => responses.push_back(response),
+            None => routes.push((method, url.to_string(), VecDeque::from([response]))),
+        }
+        self
+    }
+
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.requests.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.requests.borrow_mut().clear();
+        self.routes.borrow_mut().clear();
+        self.responses.borrow_mut().clear();
+    }
+}
+
+#[async_trait(?Send)]
+impl Transport for MockTransport {
+    async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+        let routed = self
+            .routes
+            .borrow_mut()
+            .iter_mut()
+            .find(|(method, url, _)| *method == request.method && *url == request.url)
+            .and_then(|(_, _, responses)| responses.pop_front());
+        self.requests.borrow_mut().push(request);
+        Ok(routed
+            .or_else(|| self.responses.borrow_mut().pop_front())
+            .unwrap_or_else(|| TransportResponse::new(204, "")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{HttpClient, Routes};
+    use twilight_model::id::Id;
+
+    fn request(method: Method, url: &str) -> TransportRequest {
+        TransportRequest {
+            method,
+            url: url.to_string(),
+            headers: vec![],
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mock = MockTransport::new();
+        mock.respond(200, "first");
+        let recording = RecordingTransport::new(mock);
+        futures::executor::block_on(recording.send(request(Method::Get, "https://a/1"))).unwrap();
+        futures::executor::block_on(recording.send(request(Method::Get, "https://a/2"))).unwrap();
+
+        let replay = MockTransport::replay(recording.exchanges());
+        let second =
+            futures::executor::block_on(replay.send(request(Method::Get, "https://a/2"))).unwrap();
+        assert_eq!(second.status, 204);
+        let first =
+            futures::executor::block_on(replay.send(request(Method::Get, "https://a/1"))).unwrap();
+        assert_eq!(first.body, b"first");
+        assert_eq!(replay.requests().len(), 2);
+    }
+
+    /// A transport with a fake timer that records the waits.
+    #[derive(Default)]
+    struct FakeTimer {
+        mock: MockTransport,
+        waits: RefCell<Vec<Duration>>,
+    }
+
+    #[async_trait(?Send)]
+    impl Transport for FakeTimer {
+        async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+            self.mock.send(request).await
+        }
+
+        async fn sleep(&self, duration: Duration) -> crate::Result<()> {
+            self.waits.borrow_mut().push(duration);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_uses_transport_timer() {
+        let transport = Rc::new(FakeTimer::default());
+        transport
+            .mock
+            .respond(429, r#"{"retry_after": 0.5, "global": false}"#)
+            .respond(200, "{}");
+        let client = HttpClient::new("token", Id::new(1)).with_transport(transport.clone());
+        futures::executor::block_on(client.request::<(), serde_json::Value>(
+            Method::Get,
+            Routes::UserMe,
+            None,
+        ))
+        .unwrap();
+        assert_eq!(*transport.waits.borrow(), vec![Duration::from_millis(500)]);
+        assert_eq!(transport.mock.requests().len(), 2);
+    }
+
+    /// A transport that answers after yielding once, with a timer that fires at once or never.
+    struct SlowTransport {
+        mock: MockTransport,
+        fires: bool,
+    }
+
+    #[async_trait(?Send)]
+    impl Transport for SlowTransport {
+        async fn send(&self, request: TransportRequest) -> crate::Result<TransportResponse> {
+            let mut yielded = false;
+            futures::future::poll_fn(|cx| {
+                if yielded {
+                    std::task::Poll::Ready(())
+                } else {
+                    yielded = true;
+                    cx.waker().wake_by_ref();
+                    std::task::Poll::Pending
+                }
+            })
+            .await;
+            self.mock.send(request).await
+        }
+
+        async fn sleep(&self, _duration: Duration) -> crate::Result<()> {
+            if !self.fires {
+                futures::future::pending::<()>().await;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_timeout_uses_transport_timer() {
+        let request = |fires| {
+            let transport = Rc::new(SlowTransport {
+                mock: MockTransport::new(),
+                fires,
+            });
+            let client = HttpClient::builder("token", Id::new(1))
+                .timeout(Duration::from_secs(2))
+                .transport(transport)
+                .build();
+            futures::executor::block_on(client.request::<(), serde_json::Value>(
+                Method::Get,
+                Routes::UserMe,
+                None,
+            ))
+        };
+        assert!(request(false).is_ok());
+        assert!(
+            matches!(request(true), Err(Error::Timeout(duration)) if duration == Duration::from_secs(2))
+        );
+    }
+}
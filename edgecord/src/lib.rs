@@ -7,20 +7,32 @@
 //!
 
 extern crate core;
+// The macros generate `::edgecord` paths, which the tests of this crate use too.
+#[cfg(test)]
+extern crate self as edgecord;
 
 pub mod application_command;
 pub mod builder;
+pub mod cooldown;
 pub mod error;
 pub mod handler;
 pub mod http;
+mod log;
+pub mod message_component;
+pub mod middleware;
+pub mod modal;
 pub mod model;
+pub mod sync;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod verify;
 
 #[doc(inline)]
 pub use async_trait::async_trait;
 #[doc(inline)]
 pub use edgecord_macros::*;
 
-pub use error::Error;
+pub use error::{ApiErrorCode, Error};
 
 pub type InteractionResponse = twilight_model::http::interaction::InteractionResponse;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,24 @@
+/*!
+Logging to the console of workers. On native targets like tests, the logs are written to stderr.
+*/
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        pub(crate) fn error(message: &str) {
+            worker::console_error!("{}", message);
+        }
+
+        pub(crate) fn warn(message: &str) {
+            worker::console_warn!("{}", message);
+        }
+    } else {
+        pub(crate) fn error(message: &str) {
+            eprintln!("error: {}", message);
+        }
+
+        pub(crate) fn warn(message: &str) {
+            eprintln!("warning: {}", message);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::application_command::i18n::Locales;
+use crate::builder::InteractionResponseBuilder;
+use crate::http::HttpClient;
+use crate::modal::Modal;
+use crate::InteractionResponse;
+use std::collections::HashMap;
+use twilight_model::application::interaction::MessageComponentInteraction;
+use twilight_model::http::interaction::InteractionResponseType;
+use worker::Env;
+
+/**
+Context for Message Component (buttons and select menus).
+**/
+pub struct ComponentContext {
+    pub interaction: Box<MessageComponentInteraction>,
+    pub values: Vec<String>,
+    pub params: HashMap<String, String>,
+    pub locale: Locales,
+    pub guild_locale: Option<Locales>,
+    pub env: Env,
+    pub ctx: worker::Context,
+    pub http: HttpClient,
+}
+
+impl ComponentContext {
+    pub fn new(
+        interaction: Box<MessageComponentInteraction>,
+        params: HashMap<String, String>,
+        env: Env,
+        ctx: worker::Context,
+        http: HttpClient,
+    ) -> Self {
+        Self {
+            values: interaction.data.values.clone(),
+            locale: Locales::from(interaction.locale.as_str()),
+            guild_locale: interaction.guild_locale.as_deref().map(Locales::from),
+            interaction,
+            params,
+            env,
+            ctx,
+            http,
+        }
+    }
+
+    /**
+    Returns the custom id of the clicked component.
+    **/
+    pub fn custom_id(&self) -> &str {
+        &self.interaction.data.custom_id
+    }
+
+    /**
+    Returns the value captured by the placeholder of the custom id pattern.
+    **/
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|x| x.as_str())
+    }
+
+    pub fn message<F>(&self, message: F) -> InteractionResponse
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        let mut builder = InteractionResponseBuilder::default();
+        message(&mut builder);
+        builder.build(InteractionResponseType::ChannelMessageWithSource)
+    }
+
+    /**
+    Edit the message the component was attached to.
+    **/
+    pub fn update_message<F>(&self, message: F) -> InteractionResponse
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        let mut builder = InteractionResponseBuilder::default();
+        message(&mut builder);
+        builder.build(InteractionResponseType::UpdateMessage)
+    }
+
+    /**
+    Acknowledge the interaction and edit the original message later.
+    **/
+    pub fn defer_update(&self) -> InteractionResponse {
+        InteractionResponse {
+            kind: InteractionResponseType::DeferredUpdateMessage,
+            data: None,
+        }
+    }
+
+    /**
+    Open the modal. You should create the modal with `Modal` derive macro.
+    **/
+    pub fn modal<M>(&self) -> InteractionResponse
+    where
+        M: Modal,
+    {
+        M::response()
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+/**
+Matcher for the custom id of message components.
+
+`Pattern` accepts placeholders like `"vote:{poll}:{choice}"`.
+Each placeholder matches non-empty text and is captured by its name.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomId {
+    Exact(String),
+    Prefix(String),
+    Pattern(String),
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+impl CustomId {
+    /**
+    Returns captured placeholders if `custom_id` matches.
+    **/
+    pub fn matches(&self, custom_id: &str) -> Option<HashMap<String, String>> {
+        match self {
+            CustomId::Exact(id) => (id == custom_id).then(HashMap::new),
+            CustomId::Prefix(prefix) => custom_id.starts_with(prefix.as_str()).then(HashMap::new),
+            CustomId::Pattern(pattern) => {
+                let mut params = HashMap::new();
+                if match_segments(&parse_pattern(pattern), custom_id, &mut params) {
+                    Some(params)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, CustomId::Exact(_))
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment<'_>> {
+    let mut segments = vec![];
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        if start > 0 {
+            segments.push(Segment::Literal(&rest[..start]));
+        }
+        segments.push(Segment::Placeholder(&rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    segments
+}
+
+fn match_segments(
+    segments: &[Segment<'_>],
+    custom_id: &str,
+    params: &mut HashMap<String, String>,
+) -> bool {
+    match segments {
+        [] => custom_id.is_empty(),
+        [Segment::Literal(literal), rest @ ..] => custom_id
+            .strip_prefix(literal)
+            .is_some_and(|remain| match_segments(rest, remain, params)),
+        [Segment::Placeholder(name)] => {
+            if custom_id.is_empty() {
+                return false;
+            }
+            params.insert(name.to_string(), custom_id.to_string());
+            true
+        }
+        [Segment::Placeholder(name), Segment::Literal(literal), rest @ ..] => {
+            for (index, _) in custom_id.match_indices(literal).filter(|(i, _)| *i > 0) {
+                if match_segments(rest, &custom_id[index + literal.len()..], params) {
+                    params.insert(name.to_string(), custom_id[..index].to_string());
+                    return true;
+                }
+            }
+            false
+        }
+        // two placeholders next to each other can't be split
+        [Segment::Placeholder(_), Segment::Placeholder(_), ..] => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CustomId;
+
+    #[test]
+    fn test_exact() {
+        let id = CustomId::Exact("vote".to_string());
+        assert!(id.matches("vote").is_some());
+        assert!(id.matches("vote:1").is_none());
+    }
+
+    #[test]
+    fn test_prefix() {
+        let id = CustomId::Prefix("page:".to_string());
+        assert!(id.matches("page:3").is_some());
+        assert!(id.matches("pages").is_none());
+    }
+
+    #[test]
+    fn test_pattern() {
+        let id = CustomId::Pattern("vote:{poll}:{choice}".to_string());
+        let params = id.matches("vote:12:yes").unwrap();
+        assert_eq!(params.get("poll").unwrap(), "12");
+        assert_eq!(params.get("choice").unwrap(), "yes");
+        assert!(id.matches("vote::yes").is_none());
+        assert!(id.matches("poll:12:yes").is_none());
+    }
+}
@@ -0,0 +1,42 @@
+pub mod context;
+pub mod custom_id;
+
+use futures::future::LocalBoxFuture;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+use crate::InteractionResponse;
+pub use context::*;
+pub use custom_id::*;
+
+type AsyncComponentFn = Rc<
+    dyn 'static
+        + Fn(ComponentContext) -> LocalBoxFuture<'static, crate::Result<InteractionResponse>>,
+>;
+
+/**
+Discord Message Component Handler.
+You should create [`Component`] with `component` macro.
+**/
+#[derive(Clone)]
+pub struct Component {
+    pub custom_id: CustomId,
+    pub action: AsyncComponentFn,
+}
+
+impl Debug for Component {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Component {{ custom_id: {:?} }}", self.custom_id)
+    }
+}
+
+impl Component {
+    pub fn matches(&self, custom_id: &str) -> Option<HashMap<String, String>> {
+        self.custom_id.matches(custom_id)
+    }
+
+    pub async fn invoke(&self, ctx: ComponentContext) -> crate::Result<InteractionResponse> {
+        (self.action)(ctx).await
+    }
+}
@@ -0,0 +1,257 @@
+/*!
+Middlewares around command invocation.
+
+A middleware receives the context and [`Next`], the rest of the chain.
+It can run the command with [`Next::run`], or short-circuit with its own response.
+
+```ignore
+struct Maintenance;
+
+#[async_trait(?Send)]
+impl Middleware for Maintenance {
+    async fn handle(
+        &self,
+        ctx: ChatInputCommandContext,
+        next: Next<'_>,
+    ) -> edgecord::Result<InteractionResponse> {
+        if ctx.env.var("MAINTENANCE").is_ok() {
+            return Ok(ctx.message(|msg| msg.content("under maintenance")));
+        }
+        next.run(ctx).await
+    }
+}
+
+InteractionHandler::builder().middleware(Maintenance);
+```
+*/
+use crate::application_command::{ChatInputCommandContext, Command};
+use crate::InteractionResponse;
+use async_trait::async_trait;
+use std::rc::Rc;
+use twilight_model::application::interaction::application_command::CommandDataOption;
+use twilight_model::application::interaction::ApplicationCommand;
+
+#[async_trait(?Send)]
+pub trait Middleware {
+    async fn handle(
+        &self,
+        ctx: ChatInputCommandContext,
+        next: Next<'_>,
+    ) -> crate::Result<InteractionResponse>;
+}
+
+/**
+The rest of the middleware chain and the command.
+**/
+pub struct Next<'a> {
+    middlewares: &'a [Rc<dyn Middleware>],
+    command: &'a Command,
+    interaction: Box<ApplicationCommand>,
+    options: Vec<CommandDataOption>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        middlewares: &'a [Rc<dyn Middleware>],
+        command: &'a Command,
+        interaction: Box<ApplicationCommand>,
+        options: Vec<CommandDataOption>,
+    ) -> Self {
+        Self {
+            middlewares,
+            command,
+            interaction,
+            options,
+        }
+    }
+
+    /**
+    The command to be invoked. If the command is a subcommand, it is the subcommand.
+    **/
+    pub fn command(&self) -> &Command {
+        self.command
+    }
+
+    /**
+    The options of the command to be invoked.
+    **/
+    pub fn options(&self) -> &[CommandDataOption] {
+        &self.options
+    }
+
+    /**
+    Run the next middleware, or invoke the command if this is the last one.
+    **/
+    pub async fn run(self, ctx: ChatInputCommandContext) -> crate::Result<InteractionResponse> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => {
+                middleware
+                    .handle(
+                        ctx,
+                        Next {
+                            middlewares: rest,
+                            ..self
+                        },
+                    )
+                    .await
+            }
+            None => {
+                self.command
+                    .invoke(ctx, self.interaction, self.options)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application_command::SubCommand;
+    use crate::builder::InteractionResponseBuilder;
+    use crate::handler::InteractionHandler;
+    use crate::testing::{TestClient, TestInteraction};
+    use crate::{command, group, Error};
+    use std::cell::RefCell;
+    use twilight_model::http::interaction::InteractionResponseType;
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    /// Records its name and runs the rest of the chain.
+    struct Record(&'static str, Log);
+
+    #[async_trait(?Send)]
+    impl Middleware for Record {
+        async fn handle(
+            &self,
+            ctx: ChatInputCommandContext,
+            next: Next<'_>,
+        ) -> crate::Result<InteractionResponse> {
+            self.1.borrow_mut().push(self.0);
+            next.run(ctx).await
+        }
+    }
+
+    struct Block;
+
+    #[async_trait(?Send)]
+    impl Middleware for Block {
+        async fn handle(
+            &self,
+            ctx: ChatInputCommandContext,
+            _next: Next<'_>,
+        ) -> crate::Result<InteractionResponse> {
+            Ok(ctx.message(|msg| msg.content("blocked")))
+        }
+    }
+
+    async fn allow(_ctx: &ChatInputCommandContext) -> crate::Result<bool> {
+        Ok(true)
+    }
+
+    async fn deny(_ctx: &ChatInputCommandContext) -> crate::Result<bool> {
+        Ok(false)
+    }
+
+    async fn deny_too(_ctx: &ChatInputCommandContext) -> crate::Result<bool> {
+        Ok(false)
+    }
+
+    #[command(description = "ping")]
+    #[check(allow)]
+    async fn ping(ctx: ChatInputCommandContext) -> InteractionResponse {
+        ctx.message(|msg| msg.content("pong"))
+    }
+
+    #[command(description = "the secret")]
+    #[check(allow)]
+    #[check(deny)]
+    #[check(deny_too)]
+    async fn secret(ctx: ChatInputCommandContext) -> InteractionResponse {
+        ctx.message(|msg| msg.content("secret"))
+    }
+
+    #[group(description = "admin commands")]
+    fn admin() -> Vec<SubCommand> {
+        vec![SubCommand::Command(ping())]
+    }
+
+    fn send(client: &TestClient, interaction: TestInteraction) -> Option<String> {
+        futures::executor::block_on(client.send(interaction))
+            .unwrap()
+            .data
+            .and_then(|data| data.content)
+    }
+
+    #[test]
+    fn test_middleware_order() {
+        let log = Log::default();
+        let mut builder = InteractionHandler::builder();
+        builder
+            .command(ping())
+            .group(admin())
+            .command_middleware("admin", Record("admin", log.clone()))
+            .middleware(Record("first", log.clone()))
+            .middleware(Record("second", log.clone()));
+        let client = TestClient::new(&mut builder);
+
+        assert_eq!(
+            send(&client, TestInteraction::chat_input("ping")).as_deref(),
+            Some("pong")
+        );
+        assert_eq!(*log.borrow(), vec!["first", "second"]);
+
+        log.borrow_mut().clear();
+        assert_eq!(
+            send(&client, TestInteraction::subcommand(&["admin", "ping"])).as_deref(),
+            Some("pong")
+        );
+        assert_eq!(*log.borrow(), vec!["first", "second", "admin"]);
+    }
+
+    #[test]
+    fn test_middleware_short_circuit() {
+        let log = Log::default();
+        let mut builder = InteractionHandler::builder();
+        builder
+            .command(ping())
+            .middleware(Record("first", log.clone()))
+            .command_middleware("ping", Block)
+            .command_middleware("ping", Record("never", log.clone()));
+        let client = TestClient::new(&mut builder);
+
+        assert_eq!(
+            send(&client, TestInteraction::chat_input("ping")).as_deref(),
+            Some("blocked")
+        );
+        assert_eq!(*log.borrow(), vec!["first"]);
+    }
+
+    #[test]
+    fn test_check_failed() {
+        let mut builder = InteractionHandler::builder();
+        builder
+            .command(ping())
+            .command(secret())
+            .on_error(|err, _| async move {
+                let content = match err {
+                    Error::CheckFailed(check) => format!("failed {}", check),
+                    _ => "other".to_string(),
+                };
+                InteractionResponseBuilder::new()
+                    .content(content)
+                    .build(InteractionResponseType::ChannelMessageWithSource)
+            });
+        let client = TestClient::new(&mut builder);
+
+        assert_eq!(
+            send(&client, TestInteraction::chat_input("ping")).as_deref(),
+            Some("pong")
+        );
+        // the checks run in order and stop at the first failure
+        assert_eq!(
+            send(&client, TestInteraction::chat_input("secret")).as_deref(),
+            Some("failed deny")
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::application_command::i18n::Locales;
+use crate::builder::InteractionResponseBuilder;
+use crate::http::HttpClient;
+use crate::InteractionResponse;
+use twilight_model::application::interaction::modal::ModalSubmitInteraction;
+use twilight_model::http::interaction::InteractionResponseType;
+use worker::Env;
+
+/**
+Context for Modal Submit.
+**/
+pub struct ModalContext {
+    pub interaction: Box<ModalSubmitInteraction>,
+    pub locale: Locales,
+    pub guild_locale: Option<Locales>,
+    pub env: Env,
+    pub ctx: worker::Context,
+    pub http: HttpClient,
+}
+
+impl ModalContext {
+    pub fn new(
+        interaction: Box<ModalSubmitInteraction>,
+        env: Env,
+        ctx: worker::Context,
+        http: HttpClient,
+    ) -> Self {
+        Self {
+            locale: Locales::from(interaction.locale.as_str()),
+            guild_locale: interaction.guild_locale.as_deref().map(Locales::from),
+            interaction,
+            env,
+            ctx,
+            http,
+        }
+    }
+
+    pub fn message<F>(&self, message: F) -> InteractionResponse
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        let mut builder = InteractionResponseBuilder::default();
+        message(&mut builder);
+        builder.build(InteractionResponseType::ChannelMessageWithSource)
+    }
+
+    /**
+    Edit the message the modal was opened from.
+    It is available only if the modal was opened by a message component.
+    **/
+    pub fn update_message<F>(&self, message: F) -> InteractionResponse
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        let mut builder = InteractionResponseBuilder::default();
+        message(&mut builder);
+        builder.build(InteractionResponseType::UpdateMessage)
+    }
+
+    pub fn defer<F>(&self, message: F) -> InteractionResponse
+    where
+        F: FnOnce(&mut InteractionResponseBuilder) -> &mut InteractionResponseBuilder,
+    {
+        let mut builder = InteractionResponseBuilder::default();
+        message(&mut builder);
+        builder.build(InteractionResponseType::DeferredChannelMessageWithSource)
+    }
+}
@@ -0,0 +1,121 @@
+pub mod context;
+
+use futures::future::LocalBoxFuture;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::rc::Rc;
+use twilight_model::application::component::Component;
+use twilight_model::application::interaction::modal::ModalInteractionData;
+use twilight_model::http::interaction::{InteractionResponseData, InteractionResponseType};
+
+use crate::handler::IntoResponseResult;
+use crate::InteractionResponse;
+pub use context::*;
+
+type AsyncModalFn = Rc<
+    dyn 'static + Fn(ModalContext) -> LocalBoxFuture<'static, crate::Result<InteractionResponse>>,
+>;
+
+/**
+Trait for modal form. You should implement it with `Modal` derive macro.
+**/
+pub trait Modal: Sized {
+    fn custom_id() -> String;
+    fn title() -> String;
+    fn components() -> Vec<Component>;
+    fn from_submit(data: &ModalInteractionData) -> crate::Result<Self>;
+
+    /**
+    Returns the response that opens this modal.
+    **/
+    fn response() -> InteractionResponse {
+        InteractionResponse {
+            kind: InteractionResponseType::Modal,
+            data: Some(InteractionResponseData {
+                allowed_mentions: None,
+                attachments: None,
+                choices: None,
+                components: Some(Self::components()),
+                content: None,
+                custom_id: Some(Self::custom_id()),
+                embeds: None,
+                flags: None,
+                title: Some(Self::title()),
+                tts: None,
+            }),
+        }
+    }
+}
+
+/**
+Returns the submitted value of the text input.
+**/
+pub fn get_value<'a>(data: &'a ModalInteractionData, custom_id: &str) -> Option<&'a str> {
+    data.components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find(|x| x.custom_id == custom_id)
+        .map(|x| x.value.as_str())
+}
+
+/**
+Discord Modal Submit Handler.
+You can register it with [`crate::builder::CommandHandlerBuilder::modal`].
+**/
+#[derive(Clone)]
+pub struct ModalSubmit {
+    pub custom_id: String,
+    pub action: AsyncModalFn,
+}
+
+impl Debug for ModalSubmit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ModalSubmit {{ custom_id: {:?} }}", self.custom_id)
+    }
+}
+
+impl ModalSubmit {
+    pub fn new<M, F, Fut>(action: F) -> Self
+    where
+        M: Modal + 'static,
+        F: Fn(ModalContext, M) -> Fut + 'static,
+        Fut: Future + 'static,
+        Fut::Output: IntoResponseResult,
+    {
+        let action = Rc::new(action);
+        Self {
+            custom_id: M::custom_id(),
+            action: Rc::new(move |ctx: ModalContext| {
+                let action = action.clone();
+                Box::pin(async move {
+                    let form = M::from_submit(&ctx.interaction.data)?;
+                    action(ctx, form).await.into_response_result()
+                })
+            }),
+        }
+    }
+
+    pub async fn invoke(&self, ctx: ModalContext) -> crate::Result<InteractionResponse> {
+        (self.action)(ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_value;
+    use twilight_model::application::interaction::modal::ModalInteractionData;
+
+    #[test]
+    fn test_get_value() {
+        let data = serde_json::from_str::<ModalInteractionData>(
+            r#"{"custom_id":"feedback","components":[
+                {"type":1,"components":[{"type":4,"custom_id":"name","value":"edge"}]},
+                {"type":1,"components":[{"type":4,"custom_id":"comment","value":""}]}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(get_value(&data, "name"), Some("edge"));
+        assert_eq!(get_value(&data, "comment"), Some(""));
+        assert_eq!(get_value(&data, "age"), None);
+    }
+}
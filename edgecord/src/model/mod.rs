@@ -1,14 +1,73 @@
 pub mod rest;
 
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use std::fmt::{Display, Formatter};
+use twilight_model::channel::ReactionType;
+use twilight_model::id::marker::EmojiMarker;
+use twilight_model::id::Id;
 pub use twilight_model::*;
 
 /// Emoji for encoding to URL Encoded.
-pub struct UrlEncodedEmoji(guild::Emoji);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlEncodedEmoji {
+    name: String,
+    id: Option<Id<EmojiMarker>>,
+}
+
+impl UrlEncodedEmoji {
+    pub fn unicode(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            id: None,
+        }
+    }
+
+    pub fn custom(name: &str, id: Id<EmojiMarker>) -> Self {
+        Self {
+            name: name.to_string(),
+            id: Some(id),
+        }
+    }
+}
+
+impl From<&guild::Emoji> for UrlEncodedEmoji {
+    fn from(emoji: &guild::Emoji) -> Self {
+        Self::custom(&emoji.name, emoji.id)
+    }
+}
+
+impl From<&ReactionType> for UrlEncodedEmoji {
+    fn from(reaction: &ReactionType) -> Self {
+        match reaction {
+            // Discord finds custom emojis by the id, so the name of a deleted emoji can be anything
+            ReactionType::Custom { id, name, .. } => {
+                Self::custom(name.as_deref().unwrap_or("_"), *id)
+            }
+            ReactionType::Unicode { name } => Self::unicode(name),
+        }
+    }
+}
 
 impl Display for UrlEncodedEmoji {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.0.name, self.0.id)?;
-        todo!("add percent encoding")
+        write!(f, "{}", utf8_percent_encode(&self.name, NON_ALPHANUMERIC))?;
+        if let Some(id) = self.id {
+            write!(f, ":{}", id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_encoded_emoji() {
+        assert_eq!(UrlEncodedEmoji::unicode("👍").to_string(), "%F0%9F%91%8D");
+        assert_eq!(
+            UrlEncodedEmoji::custom("party", Id::new(123)).to_string(),
+            "party:123"
+        );
     }
 }
@@ -0,0 +1,155 @@
+use serde::Serialize;
+use twilight_model::channel::thread::AutoArchiveDuration;
+use twilight_model::channel::{ChannelType, VideoQualityMode};
+use twilight_model::guild::Permissions;
+use twilight_model::http::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType};
+use twilight_model::id::marker::{ApplicationMarker, ChannelMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_model::invite::TargetType;
+
+/**
+The body of `PATCH /channels/{channel.id}`. The thread fields are only for threads.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyChannel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChannelType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+    /// `Some(None)` removes the topic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_overwrites: Option<Vec<PermissionOverwrite>>,
+    /// `Some(None)` moves the channel out of the category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Option<Id<ChannelMarker>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtc_region: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_quality_mode: Option<VideoQualityMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_auto_archive_duration: Option<AutoArchiveDuration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<AutoArchiveDuration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitable: Option<bool>,
+}
+
+/**
+The body of `POST /guilds/{guild.id}/channels`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateGuildChannel {
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChannelType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_overwrites: Option<Vec<PermissionOverwrite>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Id<ChannelMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_auto_archive_duration: Option<AutoArchiveDuration>,
+}
+
+impl CreateGuildChannel {
+    pub fn new(name: &str, kind: ChannelType) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: Some(kind),
+            topic: None,
+            bitrate: None,
+            user_limit: None,
+            rate_limit_per_user: None,
+            position: None,
+            permission_overwrites: None,
+            parent_id: None,
+            nsfw: None,
+            default_auto_archive_duration: None,
+        }
+    }
+}
+
+/**
+An element of the body of `PATCH /guilds/{guild.id}/channels`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifyGuildChannelPosition {
+    pub id: Id<ChannelMarker>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_permissions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Option<Id<ChannelMarker>>>,
+}
+
+/**
+The body of `PUT /channels/{channel.id}/permissions/{overwrite.id}`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct EditChannelPermissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deny: Option<Permissions>,
+    #[serde(rename = "type")]
+    pub kind: PermissionOverwriteType,
+}
+
+/**
+The body of `POST /channels/{channel.id}/invites`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateChannelInvite {
+    /// Seconds until the invite expires. 0 never expires.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u32>,
+    /// 0 is unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporary: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<TargetType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_user_id: Option<Id<UserMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_application_id: Option<Id<ApplicationMarker>>,
+}
+
+/**
+The body of `POST /channels/{channel.id}/followers`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct FollowAnnouncementChannel {
+    pub webhook_channel_id: Id<ChannelMarker>,
+}
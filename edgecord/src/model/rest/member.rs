@@ -0,0 +1,69 @@
+use serde::Serialize;
+use twilight_model::id::marker::{ChannelMarker, RoleMarker, UserMarker};
+use twilight_model::id::Id;
+use twilight_model::util::Timestamp;
+
+/**
+The query of `GET /guilds/{guild.id}/members`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListGuildMembers {
+    /// 1-1000, the default is 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Id<UserMarker>>,
+}
+
+/**
+The query of `GET /guilds/{guild.id}/members/search`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchGuildMembers {
+    /// Matched with the username and the nickname.
+    pub query: String,
+    /// 1-1000, the default is 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+/**
+The body of `PATCH /guilds/{guild.id}/members/{user.id}`.
+`Some(None)` resets the field.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyGuildMember {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<Id<RoleMarker>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+    /// The voice channel to move the member to. `Some(None)` disconnects the member.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Option<Id<ChannelMarker>>>,
+    /// Time out the member until the time, up to 28 days in the future.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<Option<Timestamp>>,
+}
+
+/**
+The body of `PATCH /guilds/{guild.id}/members/@me`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyCurrentMember {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<Option<String>>,
+}
+
+/**
+The body of `PUT /guilds/{guild.id}/bans/{user.id}`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateGuildBan {
+    /// 0-7 days of the messages to delete.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_message_days: Option<u8>,
+}
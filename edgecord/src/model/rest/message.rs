@@ -0,0 +1,91 @@
+use serde::Serialize;
+use twilight_model::application::component::Component;
+use twilight_model::channel::embed::Embed;
+use twilight_model::channel::message::{AllowedMentions, MessageFlags, MessageReference};
+use twilight_model::http::attachment::Attachment;
+use twilight_model::id::marker::{MessageMarker, StickerMarker, UserMarker};
+use twilight_model::id::Id;
+
+/**
+The body of `POST /channels/{channel.id}/messages`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_reference: Option<MessageReference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sticker_ids: Option<Vec<Id<StickerMarker>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+    /// The files are sent as multipart/form-data parts and the metadata are sent in `payload_json`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+}
+
+/**
+The body of `PATCH /channels/{channel.id}/messages/{message.id}`.
+`Some(None)` removes the field from the message.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Option<Vec<Embed>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Option<Vec<Component>>>,
+    /// The attachments to keep and to upload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+}
+
+/**
+The query of `GET /channels/{channel.id}/messages`. Only one of `around`, `before` and `after` can be set.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetChannelMessages {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub around: Option<Id<MessageMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Id<MessageMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Id<MessageMarker>>,
+    /// 1-100, the default is 50.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+}
+
+/**
+The query of `GET /channels/{channel.id}/messages/{message.id}/reactions/{emoji}`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GetReactions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Id<UserMarker>>,
+    /// 1-100, the default is 25.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+}
+
+/**
+The body of `POST /channels/{channel.id}/messages/bulk-delete`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkDeleteMessages {
+    /// 2-100 messages which are not older than 2 weeks.
+    pub messages: Vec<Id<MessageMarker>>,
+}
@@ -1 +1,6 @@
+pub mod channel;
+pub mod member;
+pub mod message;
+pub mod role;
+pub mod thread;
 pub mod webhook;
@@ -0,0 +1,36 @@
+use serde::Serialize;
+use twilight_model::guild::Permissions;
+use twilight_model::id::marker::RoleMarker;
+use twilight_model::id::Id;
+
+/**
+The body of `POST /guilds/{guild.id}/roles` and `PATCH /guilds/{guild.id}/roles/{role.id}`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyGuildRole {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hoist: Option<bool>,
+    /// The image data URI of the icon. `Some(None)` removes the icon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unicode_emoji: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mentionable: Option<bool>,
+}
+
+/**
+An element of the body of `PATCH /guilds/{guild.id}/roles`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifyGuildRolePosition {
+    pub id: Id<RoleMarker>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<i64>,
+}
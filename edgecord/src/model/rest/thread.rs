@@ -0,0 +1,82 @@
+use serde::Serialize;
+use twilight_model::channel::thread::AutoArchiveDuration;
+use twilight_model::channel::ChannelType;
+use twilight_model::id::marker::ChannelMarker;
+use twilight_model::id::Id;
+use twilight_model::util::Timestamp;
+
+/**
+The body of `POST /channels/{channel.id}/messages/{message.id}/threads`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct StartThreadFromMessage {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<AutoArchiveDuration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u16>,
+}
+
+impl StartThreadFromMessage {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            auto_archive_duration: None,
+            rate_limit_per_user: None,
+        }
+    }
+}
+
+/**
+The body of `POST /channels/{channel.id}/threads`.
+**/
+#[derive(Debug, Clone, Serialize)]
+pub struct StartThreadWithoutMessage {
+    pub name: String,
+    /// The default is a private thread.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChannelType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<AutoArchiveDuration>,
+    /// Whether non-moderators can add other non-moderators to a private thread.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u16>,
+}
+
+impl StartThreadWithoutMessage {
+    pub fn new(name: &str, kind: ChannelType) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: Some(kind),
+            auto_archive_duration: None,
+            invitable: None,
+            rate_limit_per_user: None,
+        }
+    }
+}
+
+/**
+The query of `GET /channels/{channel.id}/threads/archived/public` and `.../private`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListArchivedThreads {
+    /// Returns threads archived before the time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Timestamp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/**
+The query of `GET /channels/{channel.id}/users/@me/threads/archived/private`.
+**/
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListJoinedArchivedThreads {
+    /// Returns threads before the thread id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Id<ChannelMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
@@ -2,8 +2,10 @@ use serde::Serialize;
 use twilight_model::application::component::Component;
 use twilight_model::channel::embed::Embed;
 use twilight_model::channel::message::{AllowedMentions, MessageFlags};
+use twilight_model::http::attachment::Attachment;
+use twilight_model::http::interaction::InteractionResponseData;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct WebhookBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
@@ -21,4 +23,22 @@ pub struct WebhookBody {
     pub components: Option<Vec<Component>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<MessageFlags>,
+    /// The files are sent as multipart/form-data parts and the metadata are sent in `payload_json`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+}
+
+impl From<InteractionResponseData> for WebhookBody {
+    fn from(data: InteractionResponseData) -> Self {
+        Self {
+            content: data.content,
+            tts: data.tts,
+            embeds: data.embeds,
+            allowed_mentions: data.allowed_mentions,
+            components: data.components,
+            flags: data.flags,
+            attachments: data.attachments,
+            ..Self::default()
+        }
+    }
 }
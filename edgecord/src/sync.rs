@@ -0,0 +1,391 @@
+/*!
+Synchronize application commands with the live application.
+
+```ignore
+let diff = CommandSync::new()
+    .command(echo())
+    .group(emojis())
+    .sync(&client, CommandScope::Global, false)
+    .await?;
+println!("{}", diff);
+```
+*/
+use crate::application_command::{Command, CommandGroup};
+use crate::handler::InteractionHandler;
+use crate::http::{CommandScope, HttpClient};
+use serde_json::{Map, Value};
+use std::fmt::{Display, Formatter};
+
+/**
+A change of an application command.
+The values are normalized JSON of the commands.
+**/
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandChange {
+    Added(Value),
+    Changed { before: Value, after: Value },
+    Removed(Value),
+}
+
+impl CommandChange {
+    pub fn name(&self) -> &str {
+        let value = match self {
+            CommandChange::Added(value) => value,
+            CommandChange::Changed { after, .. } => after,
+            CommandChange::Removed(value) => value,
+        };
+        value
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+}
+
+/**
+The difference between the local commands and the registered commands.
+**/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandDiff {
+    pub changes: Vec<CommandChange>,
+}
+
+impl CommandDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn added(&self) -> impl Iterator<Item = &CommandChange> {
+        self.changes
+            .iter()
+            .filter(|x| matches!(x, CommandChange::Added(_)))
+    }
+
+    pub fn changed(&self) -> impl Iterator<Item = &CommandChange> {
+        self.changes
+            .iter()
+            .filter(|x| matches!(x, CommandChange::Changed { .. }))
+    }
+
+    pub fn removed(&self) -> impl Iterator<Item = &CommandChange> {
+        self.changes
+            .iter()
+            .filter(|x| matches!(x, CommandChange::Removed(_)))
+    }
+}
+
+impl Display for CommandDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "commands are up to date");
+        }
+        for change in &self.changes {
+            match change {
+                CommandChange::Added(value) => {
+                    writeln!(f, "+ {}", change.name())?;
+                    writeln!(f, "    {}", value)?;
+                }
+                CommandChange::Changed { before, after } => {
+                    writeln!(f, "~ {}", change.name())?;
+                    writeln!(f, "  - {}", before)?;
+                    writeln!(f, "  + {}", after)?;
+                }
+                CommandChange::Removed(_) => writeln!(f, "- {}", change.name())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+A set of local commands to synchronize.
+**/
+#[derive(Debug, Clone, Default)]
+pub struct CommandSync {
+    commands: Vec<Value>,
+}
+
+impl CommandSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Collect all commands and groups registered in the handler.
+    **/
+    pub fn from_handler(handler: &InteractionHandler) -> Self {
+        let mut sync = Self::new();
+        for command in &handler.commands {
+            sync.command(command.clone());
+        }
+        for group in &handler.groups {
+            sync.group(group.clone());
+        }
+        sync
+    }
+
+    pub fn command(&mut self, command: Command) -> &mut Self {
+        self.commands.push(serde_json::to_value(&command).unwrap());
+        self
+    }
+
+    pub fn group(&mut self, group: CommandGroup) -> &mut Self {
+        self.commands.push(serde_json::to_value(&group).unwrap());
+        self
+    }
+
+    pub fn commands(&self) -> &[Value] {
+        &self.commands
+    }
+
+    /**
+    Compute the difference from the registered commands in the scope.
+    **/
+    pub fn diff(&self, scope: CommandScope, registered: &[Value]) -> CommandDiff {
+        let local = self
+            .commands
+            .iter()
+            .map(|x| normalize(x, scope))
+            .collect::<Vec<_>>();
+        let remote = registered
+            .iter()
+            .map(|x| normalize(x, scope))
+            .collect::<Vec<_>>();
+        let mut changes = vec![];
+
+        for after in &local {
+            match remote.iter().find(|x| same_command(x, after)) {
+                None => changes.push(CommandChange::Added(after.clone())),
+                Some(before) if before != after => changes.push(CommandChange::Changed {
+                    before: before.clone(),
+                    after: after.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for before in &remote {
+            if !local.iter().any(|x| same_command(x, before)) {
+                changes.push(CommandChange::Removed(before.clone()));
+            }
+        }
+        CommandDiff { changes }
+    }
+
+    /**
+    Fetch the registered commands and overwrite them only if something changed.
+    If `dry_run` is true, it doesn't push the commands.
+    **/
+    pub async fn sync(
+        &self,
+        client: &HttpClient,
+        scope: CommandScope,
+        dry_run: bool,
+    ) -> crate::Result<CommandDiff> {
+        let registered = client.get_application_commands(scope).await?;
+        let diff = self.diff(scope, &registered);
+        if !diff.is_empty() && !dry_run {
+            client
+                .bulk_overwrite_application_commands(scope, &self.commands)
+                .await?;
+        }
+        Ok(diff)
+    }
+}
+
+fn same_command(a: &Value, b: &Value) -> bool {
+    a.get("name") == b.get("name")
+        && a.get("type").and_then(Value::as_u64).unwrap_or(1)
+            == b.get("type").and_then(Value::as_u64).unwrap_or(1)
+}
+
+/**
+Remove the fields which Discord fills and the default values so that the commands can be compared.
+Guild commands don't have `dm_permission`, so it is removed in the guild scope.
+**/
+fn normalize(value: &Value, scope: CommandScope) -> Value {
+    let mut value = strip_defaults(value);
+    if let Value::Object(map) = &mut value {
+        for key in [
+            "id",
+            "application_id",
+            "guild_id",
+            "version",
+            "default_permission",
+        ] {
+            map.remove(key);
+        }
+        if matches!(scope, CommandScope::Guild(_))
+            || map.get("dm_permission") == Some(&Value::Bool(true))
+        {
+            map.remove("dm_permission");
+        }
+        if !map.contains_key("type") {
+            map.insert("type".to_string(), Value::from(1));
+        }
+    }
+    value
+}
+
+/// The fields of commands and options which are the same as omitted when they are false.
+const FALSE_BY_DEFAULT: [&str; 3] = ["required", "autocomplete", "nsfw"];
+
+fn strip_defaults(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), strip_defaults(v)))
+                .filter(|(k, v)| !is_default(k, v))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(strip_defaults).collect()),
+        value => value.clone(),
+    }
+}
+
+fn is_default(key: &str, value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(false) => FALSE_BY_DEFAULT.contains(&key),
+        Value::Array(values) => values.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+/**
+Run the command line interface to synchronize commands.
+It reads `DISCORD_BOT_TOKEN` and `APPLICATION_ID` from environment variables.
+
+Arguments:
+- `--guild <id>`: Synchronize the commands of the guild instead of global commands.
+- `--dry-run`: Print the difference without pushing commands.
+**/
+#[cfg(all(not(target_arch = "wasm32"), feature = "local"))]
+pub async fn run_cli(sync: &CommandSync) -> crate::Result<CommandDiff> {
+    use std::str::FromStr;
+    use twilight_model::id::Id;
+
+    let env = |name: &str| {
+        std::env::var(name).unwrap_or_else(|_| panic!("environment variable {} is not set", name))
+    };
+    let token = env("DISCORD_BOT_TOKEN");
+    let application_id = Id::from_str(&env("APPLICATION_ID")).expect("invalid APPLICATION_ID");
+
+    let args = std::env::args().collect::<Vec<_>>();
+    let dry_run = args.iter().any(|x| x == "--dry-run");
+    let scope = match args.iter().position(|x| x == "--guild") {
+        Some(i) => CommandScope::Guild(
+            args.get(i + 1)
+                .and_then(|x| Id::from_str(x).ok())
+                .expect("--guild requires a guild id"),
+        ),
+        None => CommandScope::Global,
+    };
+
+    let client = HttpClient::new(&token, application_id);
+    let diff = sync.sync(&client, scope, dry_run).await?;
+    print!("{}", diff);
+    if dry_run && !diff.is_empty() {
+        println!("dry run: commands are not pushed");
+    }
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff() {
+        let sync = CommandSync {
+            commands: vec![
+                json!({"type": 1, "name": "echo", "description": "echo", "options": []}),
+                json!({"type": 1, "name": "ping", "description": "new description", "options": []}),
+                json!({"type": 2, "name": "info", "description": "", "options": []}),
+            ],
+        };
+        let registered = vec![
+            json!({"id": "1", "application_id": "2", "version": "3", "type": 1, "name": "echo", "description": "echo", "dm_permission": true, "default_member_permissions": null}),
+            json!({"id": "4", "application_id": "2", "version": "3", "type": 1, "name": "ping", "description": "ping"}),
+            json!({"id": "5", "application_id": "2", "version": "3", "type": 1, "name": "old", "description": "old"}),
+        ];
+        let diff = sync.diff(CommandScope::Global, &registered);
+        assert_eq!(
+            diff.added().map(|x| x.name()).collect::<Vec<_>>(),
+            vec!["info"]
+        );
+        assert_eq!(
+            diff.changed().map(|x| x.name()).collect::<Vec<_>>(),
+            vec!["ping"]
+        );
+        assert_eq!(
+            diff.removed().map(|x| x.name()).collect::<Vec<_>>(),
+            vec!["old"]
+        );
+    }
+
+    #[test]
+    fn test_diff_dm_permission() {
+        let sync = CommandSync {
+            commands: vec![
+                json!({"type": 1, "name": "ban", "description": "ban", "dm_permission": false}),
+                json!({"type": 1, "name": "echo", "description": "echo", "options": [{"type": 3, "name": "text", "description": "text", "required": false}]}),
+            ],
+        };
+        let registered = vec![
+            json!({"id": "1", "type": 1, "name": "ban", "description": "ban", "dm_permission": true}),
+            json!({"id": "2", "type": 1, "name": "echo", "description": "echo", "dm_permission": true, "options": [{"type": 3, "name": "text", "description": "text"}]}),
+        ];
+        let diff = sync.diff(CommandScope::Global, &registered);
+        assert_eq!(
+            diff.changed().map(|x| x.name()).collect::<Vec<_>>(),
+            vec!["ban"]
+        );
+        // guild commands are returned without dm_permission
+        let guild = vec![
+            json!({"id": "1", "type": 1, "name": "ban", "description": "ban"}),
+            registered[1].clone(),
+        ];
+        assert!(sync
+            .diff(CommandScope::Guild(twilight_model::id::Id::new(1)), &guild)
+            .is_empty());
+
+        let registered = vec![
+            json!({"id": "1", "type": 1, "name": "ban", "description": "ban", "dm_permission": false}),
+            registered[1].clone(),
+        ];
+        assert!(sync
+            .diff(CommandScope::Global, &registered)
+            .changed()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_diff_localizations() {
+        let sync = CommandSync {
+            commands: vec![
+                json!({"type": 1, "name": "ban", "description": "ban", "name_localizations": {"ja": "バン"}, "description_localizations": {"ja": "バンする"}}),
+                json!({"type": 1, "name": "echo", "description": "echo"}),
+            ],
+        };
+        let registered = vec![
+            json!({"id": "1", "type": 1, "name": "ban", "description": "ban", "name_localizations": {"ja": "バン"}, "description_localizations": {"ja": "バンする"}}),
+            json!({"id": "2", "type": 1, "name": "echo", "description": "echo", "name_localizations": null, "description_localizations": null, "nsfw": false}),
+        ];
+        assert!(sync.diff(CommandScope::Global, &registered).is_empty());
+    }
+
+    #[test]
+    fn test_fetch_with_localizations() {
+        let mock = std::rc::Rc::new(crate::http::transport::MockTransport::new());
+        mock.respond(200, "[]");
+        let client =
+            HttpClient::new("token", twilight_model::id::Id::new(1)).with_transport(mock.clone());
+        futures::executor::block_on(client.get_application_commands(CommandScope::Global)).unwrap();
+        assert_eq!(
+            mock.requests()[0].url,
+            "https://discord.com/api/v10/applications/1/commands?with_localizations=true"
+        );
+    }
+}
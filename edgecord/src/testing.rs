@@ -0,0 +1,650 @@
+/*!
+Test utilities to run interactions through the handler on native targets.
+They are available with `testing` feature, such as in `dev-dependencies`.
+
+[`TestClient`] signs the payloads of [`TestInteraction`] with a test keypair,
+verifies and dispatches them like [`InteractionHandler::process`], and returns the response.
+The REST calls of the commands are recorded by [`MockTransport`] instead of being sent to Discord.
+
+Deferred work with `wait_until` and the bindings of `Env` are not available on native targets.
+
+```ignore
+#[tokio::test]
+async fn test_echo() {
+    let mut builder = InteractionHandler::builder();
+    builder.command(echo());
+    let client = TestClient::new(&mut builder);
+
+    let response = client
+        .send(TestInteraction::chat_input("echo").option("text", "hello"))
+        .await
+        .unwrap();
+    assert_eq!(response.data.unwrap().content.as_deref(), Some("hello"));
+}
+```
+*/
+use crate::builder::CommandHandlerBuilder;
+use crate::handler::InteractionHandler;
+use crate::http::now_millis;
+pub use crate::http::transport::MockTransport;
+use crate::{Error, InteractionResponse};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use serde_json::{json, Value};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use twilight_model::application::interaction::Interaction;
+use worker::wasm_bindgen::{JsCast, JsValue};
+
+pub const TEST_TOKEN: &str = "test-token";
+pub const TEST_APPLICATION_ID: u64 = 100000000000000001;
+pub const TEST_USER_ID: u64 = 100000000000000002;
+pub const TEST_CHANNEL_ID: u64 = 100000000000000003;
+pub const TEST_GUILD_ID: u64 = 100000000000000004;
+
+/// The increment of interaction ids so that the replay protection doesn't reject them.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/**
+An empty `Env`. Accessing its bindings panics on native targets.
+**/
+pub fn test_env() -> worker::Env {
+    JsValue::UNDEFINED.unchecked_into()
+}
+
+/**
+An empty `Context`. `wait_until` panics on native targets.
+**/
+pub fn test_context() -> worker::Context {
+    worker::Context::new(JsValue::UNDEFINED.unchecked_into())
+}
+
+/**
+A keypair to sign test payloads. The default keypair is fixed, so the public key is stable.
+**/
+pub struct TestKeypair {
+    keypair: Keypair,
+}
+
+impl Default for TestKeypair {
+    fn default() -> Self {
+        Self::from_seed([7; 32])
+    }
+}
+
+impl TestKeypair {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let secret = SecretKey::from_bytes(&seed).unwrap();
+        let public = PublicKey::from(&secret);
+        Self {
+            keypair: Keypair { secret, public },
+        }
+    }
+
+    /**
+    The hex encoded public key for `CommandHandlerBuilder::public_key`.
+    **/
+    pub fn public_key(&self) -> String {
+        hex::encode(self.keypair.public.to_bytes())
+    }
+
+    /**
+    Sign the body like Discord. Returns the hex encoded signature.
+    **/
+    pub fn sign(&self, timestamp: &str, body: &[u8]) -> String {
+        let mut message = timestamp.as_bytes().to_vec();
+        message.extend_from_slice(body);
+        hex::encode(self.keypair.sign(&message).to_bytes())
+    }
+}
+
+/**
+A signed interaction request.
+**/
+#[derive(Debug, Clone)]
+pub struct SignedPayload {
+    pub signature: String,
+    pub timestamp: String,
+    pub body: Vec<u8>,
+}
+
+/**
+A builder of interaction payloads.
+**/
+#[derive(Debug, Clone)]
+pub struct TestInteraction {
+    kind: u8,
+    id: u64,
+    name: Option<String>,
+    subcommands: Vec<String>,
+    options: Vec<Value>,
+    data: Value,
+    user_id: u64,
+    channel_id: u64,
+    guild_id: Option<u64>,
+    locale: String,
+    guild_locale: Option<String>,
+}
+
+impl TestInteraction {
+    fn new(kind: u8, data: Value) -> Self {
+        Self {
+            kind,
+            id: ((now_millis().saturating_sub(1_420_070_400_000)) << 22)
+                + NEXT_ID.fetch_add(1, Ordering::Relaxed) % (1 << 22),
+            name: None,
+            subcommands: vec![],
+            options: vec![],
+            data,
+            user_id: TEST_USER_ID,
+            channel_id: TEST_CHANNEL_ID,
+            guild_id: Some(TEST_GUILD_ID),
+            locale: "en-US".to_string(),
+            guild_locale: Some("en-US".to_string()),
+        }
+    }
+
+    /**
+    A slash command.
+    **/
+    pub fn chat_input(name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            ..Self::new(2, Value::Null)
+        }
+    }
+
+    /**
+    A subcommand like `/group sub` or `/group sub_group sub`.
+    **/
+    pub fn subcommand(path: &[&str]) -> Self {
+        let (name, subcommands) = path.split_first().expect("path must not be empty");
+        Self {
+            subcommands: subcommands.iter().map(|x| x.to_string()).collect(),
+            ..Self::chat_input(name)
+        }
+    }
+
+    /**
+    An autocomplete of the command. Add the focused option with [`focused`](Self::focused).
+    **/
+    pub fn autocomplete(path: &[&str]) -> Self {
+        Self {
+            kind: 4,
+            ..Self::subcommand(path)
+        }
+    }
+
+    /**
+    A click of the button.
+    **/
+    pub fn button(custom_id: &str) -> Self {
+        Self::new(3, json!({"custom_id": custom_id, "component_type": 2}))
+    }
+
+    /**
+    A selection of the select menu.
+    **/
+    pub fn select_menu(custom_id: &str, values: &[&str]) -> Self {
+        Self::new(
+            3,
+            json!({"custom_id": custom_id, "component_type": 3, "values": values}),
+        )
+    }
+
+    /**
+    A submission of the modal. Add the fields with [`field`](Self::field).
+    **/
+    pub fn modal_submit(custom_id: &str) -> Self {
+        Self::new(5, json!({"custom_id": custom_id, "components": []}))
+    }
+
+    /**
+    Add an option. The type is inferred from the value: string, integer, number or boolean.
+    **/
+    pub fn option<T: Into<Value>>(mut self, name: &str, value: T) -> Self {
+        let value = value.into();
+        let kind = match &value {
+            Value::Bool(_) => 5,
+            Value::Number(x) if x.is_f64() => 10,
+            Value::Number(_) => 4,
+            _ => 3,
+        };
+        self.options
+            .push(json!({"name": name, "type": kind, "value": value}));
+        self
+    }
+
+    /**
+    Add an option with the type, like user (6), channel (7) and role (8) options.
+    **/
+    pub fn option_with_type<T: Into<Value>>(mut self, name: &str, kind: u8, value: T) -> Self {
+        self.options
+            .push(json!({"name": name, "type": kind, "value": value.into()}));
+        self
+    }
+
+    /**
+    Add the focused string option of autocomplete.
+    **/
+    pub fn focused(mut self, name: &str, value: &str) -> Self {
+        self.options
+            .push(json!({"name": name, "type": 3, "value": value, "focused": true}));
+        self
+    }
+
+    /**
+    Add a text input of the modal.
+    **/
+    pub fn field(mut self, custom_id: &str, value: &str) -> Self {
+        if let Some(Value::Array(rows)) = self.data.get_mut("components") {
+            rows.push(json!({
+                "type": 1,
+                "components": [{"type": 4, "custom_id": custom_id, "value": value}],
+            }));
+        }
+        self
+    }
+
+    pub fn user(mut self, user_id: u64) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    pub fn channel(mut self, channel_id: u64) -> Self {
+        self.channel_id = channel_id;
+        self
+    }
+
+    pub fn guild(mut self, guild_id: u64) -> Self {
+        self.guild_id = Some(guild_id);
+        self
+    }
+
+    /**
+    Invoke in DMs.
+    **/
+    pub fn dm(mut self) -> Self {
+        self.guild_id = None;
+        self.guild_locale = None;
+        self
+    }
+
+    pub fn locale(mut self, locale: &str) -> Self {
+        self.locale = locale.to_string();
+        self
+    }
+
+    pub fn guild_locale(mut self, locale: &str) -> Self {
+        self.guild_locale = Some(locale.to_string());
+        self
+    }
+
+    fn command_data(&self) -> Value {
+        let options = self.subcommands.iter().enumerate().rev().fold(
+            self.options.clone(),
+            |options, (i, name)| {
+                let kind = if i + 1 == self.subcommands.len() {
+                    1
+                } else {
+                    2
+                };
+                vec![json!({"name": name, "type": kind, "options": options})]
+            },
+        );
+        json!({
+            "id": TEST_APPLICATION_ID.to_string(),
+            "name": self.name,
+            "type": 1,
+            "options": options,
+        })
+    }
+
+    fn user_json(&self) -> Value {
+        json!({
+            "id": self.user_id.to_string(),
+            "username": "tester",
+            "discriminator": "0001",
+            "avatar": null,
+        })
+    }
+
+    /**
+    The JSON payload of the interaction.
+    **/
+    pub fn to_json(&self) -> Value {
+        let mut payload = json!({
+            "id": self.id.to_string(),
+            "application_id": TEST_APPLICATION_ID.to_string(),
+            "type": self.kind,
+            "token": "test-interaction-token",
+            "version": 1,
+            "channel_id": self.channel_id.to_string(),
+            "locale": self.locale,
+        });
+        let map = payload.as_object_mut().unwrap();
+        map.insert(
+            "data".to_string(),
+            match self.name {
+                Some(_) => self.command_data(),
+                None => self.data.clone(),
+            },
+        );
+        match self.guild_id {
+            Some(guild_id) => {
+                map.insert("guild_id".to_string(), Value::from(guild_id.to_string()));
+                map.insert(
+                    "member".to_string(),
+                    json!({
+                        "user": self.user_json(),
+                        "roles": [],
+                        "joined_at": "2022-01-01T00:00:00+00:00",
+                        "deaf": false,
+                        "mute": false,
+                        "permissions": "0",
+                    }),
+                );
+            }
+            None => {
+                map.insert("user".to_string(), self.user_json());
+            }
+        }
+        if let Some(locale) = &self.guild_locale {
+            map.insert("guild_locale".to_string(), Value::from(locale.clone()));
+        }
+        if self.kind == 3 {
+            map.insert(
+                "message".to_string(),
+                json!({
+                    "id": self.id.to_string(),
+                    "channel_id": self.channel_id.to_string(),
+                    "author": self.user_json(),
+                    "content": "",
+                    "timestamp": "2022-01-01T00:00:00+00:00",
+                    "edited_timestamp": null,
+                    "tts": false,
+                    "mention_everyone": false,
+                    "mentions": [],
+                    "mention_roles": [],
+                    "attachments": [],
+                    "embeds": [],
+                    "pinned": false,
+                    "type": 0,
+                }),
+            );
+        }
+        payload
+    }
+
+    pub fn build(&self) -> Interaction {
+        serde_json::from_value(self.to_json()).expect("invalid test interaction")
+    }
+}
+
+/**
+A client that sends signed interactions to the handler.
+**/
+pub struct TestClient {
+    handler: InteractionHandler,
+    keypair: TestKeypair,
+    http: Rc<MockTransport>,
+}
+
+impl TestClient {
+    /**
+    Build the handler with the test keypair, token and application id, and the mock of Discord API.
+    **/
+    pub fn new(builder: &mut CommandHandlerBuilder) -> Self {
+        let keypair = TestKeypair::new();
+        let http = Rc::new(MockTransport::new());
+        let handler = builder
+            .public_key(&keypair.public_key())
+            .token(TEST_TOKEN)
+            .application_id(&TEST_APPLICATION_ID.to_string())
+            .transport(http.clone())
+            .build()
+            .expect("failed to build the handler");
+        Self {
+            handler,
+            keypair,
+            http,
+        }
+    }
+
+    pub fn handler(&self) -> &InteractionHandler {
+        &self.handler
+    }
+
+    /**
+    The mock of Discord API that records the REST calls.
+    **/
+    pub fn http(&self) -> &MockTransport {
+        &self.http
+    }
+
+    pub fn sign(&self, interaction: &TestInteraction) -> SignedPayload {
+        let body = serde_json::to_vec(&interaction.to_json()).unwrap();
+        let timestamp = (now_millis() / 1000).to_string();
+        SignedPayload {
+            signature: self.keypair.sign(&timestamp, &body),
+            timestamp,
+            body,
+        }
+    }
+
+    /**
+    Sign and send the interaction. It is an error if the interaction type is not supported.
+    **/
+    pub async fn send(&self, interaction: TestInteraction) -> crate::Result<InteractionResponse> {
+        self.send_payload(&self.sign(&interaction))
+            .await?
+            .ok_or_else(|| Error::InvalidResponse("the interaction is not supported".to_string()))
+    }
+
+    pub async fn send_payload(
+        &self,
+        payload: &SignedPayload,
+    ) -> crate::Result<Option<InteractionResponse>> {
+        self.handler
+            .handle_payload(
+                Some(&payload.signature),
+                Some(&payload.timestamp),
+                &payload.body,
+                test_env(),
+                test_context(),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application_command::{
+        AutocompleteContext, ChatInputCommandContext, Choice, ChoiceValue, SubCommand,
+    };
+    use crate::message_component::ComponentContext;
+    use crate::{command, component, group};
+    use twilight_model::application::command::CommandOptionChoice;
+    use twilight_model::application::interaction::application_command::CommandOptionValue;
+    use twilight_model::application::interaction::application_command_autocomplete::ApplicationCommandAutocompleteDataOption;
+    use twilight_model::http::interaction::InteractionResponseType;
+    use worker::Method;
+
+    async fn complete_fruit(
+        _ctx: AutocompleteContext,
+        value: String,
+        _options: Vec<ApplicationCommandAutocompleteDataOption>,
+    ) -> Vec<Choice> {
+        ["apple", "banana"]
+            .iter()
+            .filter(|x| x.starts_with(&value))
+            .map(|x| Choice {
+                name: x.to_string(),
+                i18n_names: None,
+                value: ChoiceValue::String(x.to_string()),
+            })
+            .collect()
+    }
+
+    #[command(description = "echo the text after typing")]
+    async fn echo(
+        ctx: ChatInputCommandContext,
+        #[option(description = "the text", autocomplete = "complete_fruit")] text: String,
+    ) -> crate::Result<InteractionResponse> {
+        ctx.http
+            .trigger_typing_indicator(ctx.interaction.channel_id)
+            .await?;
+        Ok(ctx.message(|msg| msg.content(&text)))
+    }
+
+    #[group(description = "show emoji")]
+    fn emoji() -> Vec<SubCommand> {
+        vec![SubCommand::Command(dog())]
+    }
+
+    #[command(description = "show dog emoji")]
+    async fn dog(
+        ctx: ChatInputCommandContext,
+        #[option(description = "the count")] count: i64,
+    ) -> InteractionResponse {
+        ctx.message(|msg| msg.content("🐶".repeat(count as usize)))
+    }
+
+    #[component(custom_id = "vote:{choice}")]
+    async fn vote(ctx: ComponentContext) -> InteractionResponse {
+        let choice = ctx.param("choice").unwrap_or_default().to_string();
+        ctx.update_message(|msg| msg.content(format!("voted for {}", choice)))
+    }
+
+    fn client() -> TestClient {
+        let mut builder = InteractionHandler::builder();
+        builder.command(echo()).group(emoji()).component(vote());
+        TestClient::new(&mut builder)
+    }
+
+    fn content(response: InteractionResponse) -> Option<String> {
+        response.data.and_then(|data| data.content)
+    }
+
+    #[test]
+    fn test_build_interactions() {
+        match TestInteraction::subcommand(&["emoji", "animal", "dog"])
+            .option("size", 3)
+            .build()
+        {
+            Interaction::ApplicationCommand(command) => {
+                assert_eq!(command.data.name, "emoji");
+                match &command.data.options[0].value {
+                    CommandOptionValue::SubCommandGroup(options) => {
+                        assert_eq!(options[0].name, "dog")
+                    }
+                    _ => panic!("not a subcommand group"),
+                }
+            }
+            _ => panic!("not a command"),
+        }
+        assert!(matches!(
+            TestInteraction::autocomplete(&["echo"])
+                .focused("text", "he")
+                .build(),
+            Interaction::ApplicationCommandAutocomplete(_)
+        ));
+        assert!(matches!(
+            TestInteraction::select_menu("menu", &["a"]).dm().build(),
+            Interaction::MessageComponent(_)
+        ));
+        assert!(matches!(
+            TestInteraction::modal_submit("form")
+                .field("name", "value")
+                .build(),
+            Interaction::ModalSubmit(_)
+        ));
+    }
+
+    #[test]
+    fn test_client() {
+        let client = TestClient::new(&mut InteractionHandler::builder());
+        let payload = client.sign(&TestInteraction::chat_input("unknown"));
+        let response = futures::executor::block_on(client.send_payload(&payload)).unwrap();
+        assert!(response.is_some());
+
+        let mut forged = payload;
+        forged.body.push(b' ');
+        assert!(matches!(
+            futures::executor::block_on(client.send_payload(&forged)),
+            Err(Error::Verification(_))
+        ));
+    }
+
+    #[test]
+    fn test_client_runs_command() {
+        let client = client();
+        let response = futures::executor::block_on(
+            client.send(TestInteraction::chat_input("echo").option("text", "hello")),
+        )
+        .unwrap();
+        assert_eq!(
+            response.kind,
+            InteractionResponseType::ChannelMessageWithSource
+        );
+        assert_eq!(content(response).as_deref(), Some("hello"));
+
+        let requests = client.http().requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::Post);
+        assert_eq!(
+            requests[0].url,
+            format!(
+                "https://discord.com/api/v10/channels/{}/typing",
+                TEST_CHANNEL_ID
+            )
+        );
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some(format!("Bot {}", TEST_TOKEN).as_str())
+        );
+    }
+
+    #[test]
+    fn test_client_runs_subcommand() {
+        let client = client();
+        let response = futures::executor::block_on(
+            client.send(TestInteraction::subcommand(&["emoji", "dog"]).option("count", 2)),
+        )
+        .unwrap();
+        assert_eq!(content(response).as_deref(), Some("🐶🐶"));
+        assert!(client.http().requests().is_empty());
+    }
+
+    #[test]
+    fn test_client_runs_autocomplete() {
+        let client = client();
+        let response = futures::executor::block_on(
+            client.send(TestInteraction::autocomplete(&["echo"]).focused("text", "ba")),
+        )
+        .unwrap();
+        assert_eq!(
+            response.kind,
+            InteractionResponseType::ApplicationCommandAutocompleteResult
+        );
+        let choices = response.data.unwrap().choices.unwrap();
+        assert_eq!(choices.len(), 1);
+        assert!(matches!(
+            &choices[0],
+            CommandOptionChoice::String { name, value, .. } if name == "banana" && value == "banana"
+        ));
+    }
+
+    #[test]
+    fn test_client_runs_component() {
+        let client = client();
+        let response =
+            futures::executor::block_on(client.send(TestInteraction::button("vote:cat"))).unwrap();
+        assert_eq!(response.kind, InteractionResponseType::UpdateMessage);
+        assert_eq!(content(response).as_deref(), Some("voted for cat"));
+    }
+}
@@ -0,0 +1,315 @@
+/*!
+Verification of interaction requests.
+
+Discord signs every interaction request with Ed25519.
+In addition to the signature, the handler checks the timestamp freshness and rejects replayed interaction ids.
+*/
+use crate::http::now_millis;
+use crate::log;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use worker::kv::KvStore;
+use worker::ObjectNamespace;
+
+pub const SIGNATURE_HEADER: &str = "X-Signature-Ed25519";
+pub const TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+
+/**
+Errors of the request verification. All of them are responded with 401.
+**/
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifyError {
+    MissingHeader(&'static str),
+    MalformedHeader(&'static str),
+    InvalidSignature,
+    /// The timestamp is older or newer than the allowed clock skew. The value is the skew in seconds.
+    StaleTimestamp(u64),
+    /// The interaction id was already processed.
+    Replayed(String),
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::MissingHeader(name) => write!(f, "header `{}` is missing", name),
+            VerifyError::MalformedHeader(name) => write!(f, "header `{}` is malformed", name),
+            VerifyError::InvalidSignature => write!(f, "invalid request signature"),
+            VerifyError::StaleTimestamp(skew) => {
+                write!(f, "request timestamp is {} seconds off", skew)
+            }
+            VerifyError::Replayed(id) => write!(f, "interaction `{}` is replayed", id),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/**
+Check the timestamp (unix seconds) is within `max_skew` seconds from now.
+**/
+pub(crate) fn check_timestamp(timestamp: u64, now: u64, max_skew: u64) -> Result<(), VerifyError> {
+    let skew = now.abs_diff(timestamp);
+    if skew > max_skew {
+        Err(VerifyError::StaleTimestamp(skew))
+    } else {
+        Ok(())
+    }
+}
+
+/**
+A store of processed interaction ids.
+
+[`DurableObjectReplayStore`] shares the ids across isolates strictly.
+**/
+#[async_trait(?Send)]
+pub trait ReplayStore {
+    /**
+    Record the interaction id for `ttl` seconds.
+    Returns false if the id is already recorded.
+    **/
+    async fn insert(&self, interaction_id: &str, ttl: u64) -> bool;
+}
+
+thread_local! {
+    /// Workers run each isolate in a single thread, so this is shared by all requests of the isolate.
+    static MEMORY_IDS: RefCell<HashMap<String, u64>> = RefCell::new(HashMap::new());
+}
+
+/**
+A replay store in memory of the isolate.
+All memory stores share the ids, so it works even if the handler is built for each request.
+The isolates are not shared across locations and can be evicted at any time.
+**/
+#[derive(Debug, Clone, Default)]
+pub struct MemoryReplayStore;
+
+impl MemoryReplayStore {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait(?Send)]
+impl ReplayStore for MemoryReplayStore {
+    async fn insert(&self, interaction_id: &str, ttl: u64) -> bool {
+        let now = now_millis();
+        MEMORY_IDS.with(|ids| {
+            let mut ids = ids.borrow_mut();
+            ids.retain(|_, expires_at| *expires_at > now);
+            if ids.contains_key(interaction_id) {
+                return false;
+            }
+            ids.insert(interaction_id.to_string(), now + ttl * 1000);
+            true
+        })
+    }
+}
+
+/**
+A replay store in Workers KV.
+KV is eventually consistent and the id is read before it is written,
+so a replay in a short time may not be detected. Use [`DurableObjectReplayStore`] to detect it strictly.
+**/
+#[derive(Clone)]
+pub struct KvReplayStore {
+    store: KvStore,
+    prefix: String,
+}
+
+impl KvReplayStore {
+    pub fn new(store: KvStore) -> Self {
+        Self {
+            store,
+            prefix: "interaction:".to_string(),
+        }
+    }
+
+    pub fn with_prefix(store: KvStore, prefix: &str) -> Self {
+        Self {
+            store,
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl ReplayStore for KvReplayStore {
+    async fn insert(&self, interaction_id: &str, ttl: u64) -> bool {
+        let key = format!("{}{}", self.prefix, interaction_id);
+        if let Ok(Some(_)) = self.store.get(&key).text().await {
+            return false;
+        }
+        // KV requires at least 60 seconds for ttl
+        let result = match self.store.put(&key, "1") {
+            Ok(builder) => builder.expiration_ttl(ttl.max(60)).execute().await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            log::error(&format!(
+                "failed to save the interaction `{}`: {}",
+                key, err
+            ));
+        }
+        true
+    }
+}
+
+/**
+A replay store in Durable Objects. Each id is recorded by its own object, so a replay is always detected.
+
+The Durable Object has to respond with [`handle_replay_request`].
+
+```ignore
+#[durable_object]
+pub struct Interactions {
+    state: State,
+}
+
+#[durable_object]
+impl DurableObject for Interactions {
+    fn new(state: State, _env: Env) -> Self {
+        Self { state }
+    }
+
+    async fn fetch(&mut self, req: Request) -> Result<Response> {
+        edgecord::verify::handle_replay_request(&mut self.state.storage(), req).await
+    }
+}
+
+InteractionHandler::builder()
+    .replay_store(DurableObjectReplayStore::new(env.durable_object("INTERACTIONS")?));
+```
+**/
+pub struct DurableObjectReplayStore {
+    namespace: ObjectNamespace,
+}
+
+impl DurableObjectReplayStore {
+    pub fn new(namespace: ObjectNamespace) -> Self {
+        Self { namespace }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReplayResult {
+    inserted: bool,
+}
+
+#[async_trait(?Send)]
+impl ReplayStore for DurableObjectReplayStore {
+    async fn insert(&self, interaction_id: &str, ttl: u64) -> bool {
+        let result = async {
+            let url = url::Url::parse_with_params(
+                "https://replay/",
+                &[("id", interaction_id.to_string()), ("ttl", ttl.to_string())],
+            )
+            .ok()?;
+            let stub = self
+                .namespace
+                .id_from_name(interaction_id)
+                .ok()?
+                .get_stub()
+                .ok()?;
+            let mut response = stub.fetch_with_str(url.as_str()).await.ok()?;
+            response.json::<ReplayResult>().await.ok()
+        };
+        // the request is not rejected if the object is unavailable, like the other stores
+        result.await.map(|x| x.inserted).unwrap_or(true)
+    }
+}
+
+/**
+Respond to a request from [`DurableObjectReplayStore`] in the Durable Object.
+A request with missing or malformed parameters is responded with 400.
+**/
+pub async fn handle_replay_request(
+    storage: &mut worker::Storage,
+    req: worker::Request,
+) -> worker::Result<worker::Response> {
+    let params = req
+        .url()?
+        .query_pairs()
+        .into_owned()
+        .collect::<HashMap<_, _>>();
+    let (id, ttl) = match parse_params(&params) {
+        Ok(params) => params,
+        Err(message) => return worker::Response::error(message, 400),
+    };
+
+    let now = now_millis();
+    let expires_at = storage.get::<u64>(id).await.ok();
+    let inserted = !matches!(expires_at, Some(x) if x > now);
+    if inserted {
+        storage.put(id, now + ttl * 1000).await?;
+    }
+    worker::Response::from_json(&ReplayResult { inserted })
+}
+
+/**
+Parse `id` and `ttl` sent by [`DurableObjectReplayStore`].
+**/
+fn parse_params(params: &HashMap<String, String>) -> Result<(&str, u64), String> {
+    let param = |name: &str| {
+        params
+            .get(name)
+            .ok_or_else(|| format!("missing parameter `{}`", name))
+    };
+    let id = param("id")?;
+    let ttl = param("ttl")?
+        .parse::<u64>()
+        .map_err(|_| "invalid parameter `ttl`".to_string())?;
+    Ok((id, ttl))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_timestamp() {
+        assert!(check_timestamp(1000, 1100, 300).is_ok());
+        assert!(check_timestamp(1100, 1000, 300).is_ok());
+        assert_eq!(
+            check_timestamp(1000, 2000, 300),
+            Err(VerifyError::StaleTimestamp(1000))
+        );
+    }
+
+    #[test]
+    fn test_memory_replay_store() {
+        let store = MemoryReplayStore::new();
+        assert!(futures::executor::block_on(store.insert("1", 60)));
+        assert!(!futures::executor::block_on(store.insert("1", 60)));
+        assert!(futures::executor::block_on(store.insert("2", 60)));
+
+        // another store in the isolate shares the ids
+        let store = MemoryReplayStore::new();
+        assert!(!futures::executor::block_on(store.insert("1", 60)));
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let params = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        assert_eq!(
+            parse_params(&params(&[("id", "1"), ("ttl", "300")])),
+            Ok(("1", 300))
+        );
+        assert_eq!(
+            parse_params(&params(&[("id", "1"), ("ttl", "5m")])),
+            Err("invalid parameter `ttl`".to_string())
+        );
+        assert_eq!(
+            parse_params(&params(&[("ttl", "300")])),
+            Err("missing parameter `id`".to_string())
+        );
+    }
+}
@@ -1,10 +1,14 @@
-use crate::utils::parse_i18n;
+// darling expands `#[darling(default)]` of `ChoicesArgs` into a match that clippy flags
+#![allow(clippy::manual_unwrap_or_default)]
+use crate::utils::parse_localizations;
 use darling::FromMeta;
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use syn::spanned::Spanned;
 use syn::Data;
 
+const CHOICE_LIMIT: usize = 25;
+
 #[derive(Debug, darling::FromMeta)]
 pub(crate) struct ChoicesMeta {
     #[darling(rename = "type")]
@@ -44,6 +48,7 @@ pub(crate) struct ChoiceArgs<T> {
 pub(crate) struct StringChoiceMeta {
     pub rename: Option<String>,
     pub i18n_names: Option<syn::Path>,
+    pub i18n: Option<String>,
     pub value: Option<String>,
 }
 
@@ -51,6 +56,7 @@ pub(crate) struct StringChoiceMeta {
 pub(crate) struct IntegerChoiceMeta {
     pub rename: Option<String>,
     pub i18n_names: Option<syn::Path>,
+    pub i18n: Option<String>,
     pub value: i64,
 }
 
@@ -58,6 +64,7 @@ pub(crate) struct IntegerChoiceMeta {
 pub(crate) struct FloatChoiceMeta {
     pub rename: Option<String>,
     pub i18n_names: Option<syn::Path>,
+    pub i18n: Option<String>,
     pub value: f64,
 }
 
@@ -120,6 +127,11 @@ pub fn expand_derive_choice(mut input: syn::DeriveInput) -> Result<TokenStream,
             ));
         }
 
+        let span = variant
+            .attrs
+            .first()
+            .map(|attr| attr.span())
+            .unwrap_or_else(|| variant.ident.span());
         let attrs = variant
             .attrs
             .drain(..)
@@ -154,11 +166,27 @@ pub fn expand_derive_choice(mut input: syn::DeriveInput) -> Result<TokenStream,
                     ident: variant.ident,
                 }
             }
-        })
+        });
+
+        validate_choice_name(&choices.last().unwrap().get_name(), span)?;
+    }
+
+    if choices.len() > CHOICE_LIMIT {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            format!(
+                "option can't have more than {} choices ({})",
+                CHOICE_LIMIT,
+                choices.len()
+            ),
+        ));
     }
 
     let enum_name = &input.ident;
-    let parsed = choices.iter().map(parse_choice).collect::<Vec<_>>();
+    let parsed = choices
+        .iter()
+        .map(parse_choice)
+        .collect::<Result<Vec<_>, _>>()?;
     let matchs = choices
         .clone()
         .iter()
@@ -192,7 +220,10 @@ pub fn expand_derive_choice(mut input: syn::DeriveInput) -> Result<TokenStream,
         use ::edgecord::model::application::command::CommandOptionType;
 
         impl ::edgecord::application_command::option::FromCommandOptionValue for #enum_name {
-            fn from_option(value: CommandOptionValue) -> ::std::result::Result<Self, ::edgecord::Error> where Self: Sized {
+            fn from_option(
+                value: CommandOptionValue,
+                _resolved: Option<&::edgecord::model::application::interaction::application_command::CommandInteractionDataResolved>,
+            ) -> ::std::result::Result<Self, ::edgecord::Error> where Self: Sized {
                 #inject
                 match value {
                     #( #matchs, )*
@@ -214,12 +245,32 @@ pub fn expand_derive_choice(mut input: syn::DeriveInput) -> Result<TokenStream,
     }))
 }
 
-fn parse_choice(choice: &Choice) -> proc_macro2::TokenStream {
+fn validate_choice_name(name: &str, span: proc_macro2::Span) -> Result<(), syn::Error> {
+    if name.is_empty() || name.chars().count() > 100 {
+        return Err(syn::Error::new(
+            span,
+            format!("choice name `{}` length must be between 1 and 100", name),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_choice_i18n(
+    path: Option<syn::Path>,
+    key: Option<&String>,
+    ident: &Ident,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    parse_localizations(path, key, "name", ident.span(), |x| {
+        validate_choice_name(x, ident.span())
+    })
+}
+
+fn parse_choice(choice: &Choice) -> Result<proc_macro2::TokenStream, syn::Error> {
     let name = choice.get_name();
-    match choice {
+    Ok(match choice {
         Choice::String { meta, ident } => {
             let value = meta.clone().value.unwrap_or_else(|| ident.to_string());
-            let i18n_names = parse_i18n(meta.i18n_names.clone());
+            let i18n_names = parse_choice_i18n(meta.i18n_names.clone(), meta.i18n.as_ref(), ident)?;
             quote::quote! {
                 ::edgecord::application_command::choice::Choice {
                     name: #name.to_string(),
@@ -228,9 +279,9 @@ fn parse_choice(choice: &Choice) -> proc_macro2::TokenStream {
                 }
             }
         }
-        Choice::Integer { meta, .. } => {
+        Choice::Integer { meta, ident } => {
             let value = meta.value;
-            let i18n_names = parse_i18n(meta.i18n_names.clone());
+            let i18n_names = parse_choice_i18n(meta.i18n_names.clone(), meta.i18n.as_ref(), ident)?;
             quote::quote! {
                 ::edgecord::application_command::choice::Choice {
                     name: #name.to_string(),
@@ -239,9 +290,9 @@ fn parse_choice(choice: &Choice) -> proc_macro2::TokenStream {
                 }
             }
         }
-        Choice::Float { meta, .. } => {
+        Choice::Float { meta, ident } => {
             let value = meta.value;
-            let i18n_names = parse_i18n(meta.i18n_names.clone());
+            let i18n_names = parse_choice_i18n(meta.i18n_names.clone(), meta.i18n.as_ref(), ident)?;
             quote::quote! {
                 ::edgecord::application_command::choice::Choice {
                     name: #name.to_string(),
@@ -250,7 +301,7 @@ fn parse_choice(choice: &Choice) -> proc_macro2::TokenStream {
                 }
             }
         }
-    }
+    })
 }
 
 fn parse_choice_matches(enum_name: &Ident, choice: &Choice) -> proc_macro2::TokenStream {
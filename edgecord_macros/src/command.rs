@@ -1,10 +1,12 @@
 use crate::channel_type::ChannelTypes;
+use crate::cooldown::{parse_cooldown, CooldownMeta};
 use crate::permission::PermissionFlagBits;
-use crate::utils::parse_i18n;
-use crate::validate::validate_option;
+use crate::utils::{arg_span, parse_dm_permission, parse_localizations};
+use crate::validate::{validate_description, validate_name, validate_option, validate_options};
 #[allow(unused_imports)]
 use darling::FromMeta as _;
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::ToTokens;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned as _;
@@ -17,7 +19,11 @@ pub(crate) struct CommandMeta {
     pub description: String,
     pub i18n_names: Option<syn::Path>,
     pub i18n_descriptions: Option<syn::Path>,
+    pub i18n: Option<String>,
     pub default_permissions: Option<PermissionFlagBits>,
+    pub dm_permission: Option<bool>,
+    pub nsfw: Option<bool>,
+    pub cooldown: Option<CooldownMeta>,
 }
 
 #[derive(Debug, darling::FromMeta)]
@@ -26,10 +32,12 @@ pub(crate) struct OptionMeta {
     pub description: String,
     pub i18n_names: Option<syn::Path>,
     pub i18n_descriptions: Option<syn::Path>,
-    #[allow(dead_code)]
+    pub i18n: Option<String>,
     pub autocomplete: Option<syn::Path>,
     pub min_value: Option<syn::Lit>,
     pub max_value: Option<syn::Lit>,
+    pub min_length: Option<u16>,
+    pub max_length: Option<u16>,
     pub channel_types: Option<ChannelTypes>,
 }
 
@@ -42,25 +50,117 @@ pub(crate) struct CommandOption {
     pub name: syn::Ident,
     pub t: syn::Type,
     pub meta: OptionMeta,
+    /// The span of the `option` attribute.
+    pub span: Span,
+    pub i18n_names: proc_macro2::TokenStream,
+    pub i18n_descriptions: proc_macro2::TokenStream,
+}
+
+impl CommandOption {
+    pub fn new(
+        name: syn::Ident,
+        t: syn::Type,
+        meta: OptionMeta,
+        span: Span,
+    ) -> Result<Self, syn::Error> {
+        let i18n_names = parse_localizations(
+            meta.i18n_names.clone(),
+            meta.i18n.as_ref(),
+            "name",
+            span,
+            |x| validate_name(x, span),
+        )?;
+        let i18n_descriptions = parse_localizations(
+            meta.i18n_descriptions.clone(),
+            meta.i18n.as_ref(),
+            "description",
+            span,
+            |x| validate_description(x, span),
+        )?;
+        Ok(Self {
+            name,
+            t,
+            meta,
+            span,
+            i18n_names,
+            i18n_descriptions,
+        })
+    }
+
+    /**
+    The option name for Discord. Default is the parameter name.
+    **/
+    pub fn option_name(&self) -> String {
+        self.meta
+            .name
+            .clone()
+            .unwrap_or_else(|| self.name.to_string())
+    }
+}
+
+/**
+A parameter of command function.
+`Options` is a struct that derives `CommandOptions` and is marked with `#[options]`.
+**/
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum CommandParam {
+    Option(CommandOption),
+    Options(syn::Type),
 }
 
 pub(crate) fn parse_command(
     args: CommandMeta,
+    nested: &[syn::NestedMeta],
     mut func: syn::ItemFn,
 ) -> Result<TokenStream, darling::Error> {
     if func.sig.asyncness.is_none() {
         return Err(syn::Error::new(func.sig.span(), "command function must be async").into());
     }
+    let name_span = match args.name {
+        Some(_) => arg_span(nested, "name"),
+        None => func.sig.ident.span(),
+    };
     let command_name = args.name.unwrap_or_else(|| func.sig.ident.to_string());
     let description = args.description;
-    let i18n_names = parse_i18n(args.i18n_names);
-    let i18n_descriptions = parse_i18n(args.i18n_descriptions);
+    validate_name(&command_name, name_span)?;
+    validate_description(&description, arg_span(nested, "description"))?;
+    let i18n_span = |field| arg_span(nested, if args.i18n.is_some() { "i18n" } else { field });
+    let i18n_names = parse_localizations(
+        args.i18n_names,
+        args.i18n.as_ref(),
+        "name",
+        i18n_span("i18n_names"),
+        |x| validate_name(x, i18n_span("i18n_names")),
+    )?;
+    let i18n_descriptions = parse_localizations(
+        args.i18n_descriptions,
+        args.i18n.as_ref(),
+        "description",
+        i18n_span("i18n_descriptions"),
+        |x| validate_description(x, i18n_span("i18n_descriptions")),
+    )?;
     let function_name = std::mem::replace(&mut func.sig.ident, syn::parse_quote! { inner });
     let visibility = &func.vis;
 
-    let options = parse_options(&mut func.sig.inputs)?;
-    let parsed_options = options.iter().map(parse_option_meta).collect::<Vec<_>>();
-    let action = parse_action(options);
+    let checks = parse_checks(&mut func.attrs)?;
+    let params = parse_options(&mut func.sig.inputs)?;
+    let parsed_options = params
+        .iter()
+        .map(|param| match param {
+            CommandParam::Option(option) => {
+                let option = parse_option_meta(option);
+                quote::quote! { vec![#option] }
+            }
+            CommandParam::Options(ty) => quote::quote! {
+                <#ty as ::edgecord::application_command::CommandOptions>::options()
+            },
+        })
+        .collect::<Vec<_>>();
+    let action = parse_action(&params, &checks);
+    let dm_permission = parse_dm_permission(args.dm_permission);
+    let nsfw = args.nsfw.unwrap_or(false);
+    let cooldown = parse_cooldown(args.cooldown, arg_span(nested, "cooldown"))?;
+    let autocomplete = parse_autocomplete(&params);
     let default_permissions = {
         match args.default_permissions {
             None => quote::quote! {None},
@@ -83,8 +183,12 @@ pub(crate) fn parse_command(
                 i18n_names: #i18n_names,
                 i18n_descriptions: #i18n_descriptions,
                 default_permissions: #default_permissions,
-                options: vec! [#( #parsed_options, )*],
+                dm_permission: #dm_permission,
+                nsfw: #nsfw,
+                options: Vec::<Vec<::edgecord::application_command::CommandOption>>::from([#( #parsed_options, )*]).concat(),
+                cooldown: #cooldown,
                 action: #action,
+                autocomplete: #autocomplete,
             }
         }
     }))
@@ -92,7 +196,7 @@ pub(crate) fn parse_command(
 
 pub(crate) fn parse_options(
     options: &mut Punctuated<FnArg, Comma>,
-) -> Result<Vec<CommandOption>, darling::Error> {
+) -> Result<Vec<CommandParam>, darling::Error> {
     let mut parsed_options = Vec::new();
 
     for option in options.iter_mut().skip(1) {
@@ -108,6 +212,21 @@ pub(crate) fn parse_options(
             x => return Err(syn::Error::new(x.span(), "name must be identifier").into()),
         };
 
+        if let Some(i) = pattern
+            .attrs
+            .iter()
+            .position(|attr| attr.path.is_ident("options"))
+        {
+            pattern.attrs.remove(i);
+            parsed_options.push(CommandParam::Options((*pattern.ty).clone()));
+            continue;
+        }
+
+        let span = pattern
+            .attrs
+            .first()
+            .map(|attr| attr.span())
+            .unwrap_or_else(|| pattern.span());
         let attrs = pattern
             .attrs
             .drain(..)
@@ -116,45 +235,159 @@ pub(crate) fn parse_options(
 
         let meta = <OptionMetaWrapped as darling::FromMeta>::from_list(&attrs)?.option;
 
-        validate_option(&meta, pattern.span())?;
-
-        parsed_options.push(CommandOption {
-            name: name.clone(),
-            t: (*pattern.ty).clone(),
-            meta,
-        })
+        let option = CommandOption::new(name.clone(), (*pattern.ty).clone(), meta, span)?;
+        validate_option(&option)?;
+        parsed_options.push(CommandParam::Option(option))
     }
+    validate_options(
+        &parsed_options
+            .iter()
+            .filter_map(|param| match param {
+                CommandParam::Option(option) => Some(option),
+                CommandParam::Options(_) => None,
+            })
+            .collect::<Vec<_>>(),
+        options.span(),
+    )?;
     Ok(parsed_options)
 }
 
-fn parse_action(options: Vec<CommandOption>) -> proc_macro2::TokenStream {
-    let args = options
+/**
+Generate the expression that extracts the option value from `options` and `resolved`.
+**/
+pub(crate) fn parse_option_value(option: &CommandOption) -> proc_macro2::TokenStream {
+    let name = match &option.meta.name {
+        None => option.name.to_string(),
+        Some(x) => x.clone(),
+    };
+    let (required, t) = parse_option_type(&option.t);
+    if required {
+        quote::quote! {
+            <#t>::from_option(
+                options.iter().find(|x| x.name == #name).cloned()
+                    .ok_or_else(|| ::edgecord::Error::MissingOption(#name.to_string()))?.value,
+                resolved,
+            ).map_err(|_| ::edgecord::Error::InvalidOption(#name.to_string()))?
+        }
+    } else {
+        quote::quote! {
+            match options.iter().find(|x| x.name == #name).cloned() {
+                Some(value) => Some(
+                    <#t>::from_option(value.value, resolved)
+                        .map_err(|_| ::edgecord::Error::InvalidOption(#name.to_string()))?
+                ),
+                None => None,
+            }
+        }
+    }
+}
+
+/**
+Take `#[check(path)]` attributes from the command function.
+**/
+fn parse_checks(attrs: &mut Vec<syn::Attribute>) -> Result<Vec<syn::Path>, darling::Error> {
+    let mut checks = vec![];
+    let mut i = 0;
+    while i < attrs.len() {
+        if attrs[i].path.is_ident("check") {
+            checks.push(attrs.remove(i).parse_args::<syn::Path>()?);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(checks)
+}
+
+fn parse_action(params: &[CommandParam], checks: &[syn::Path]) -> proc_macro2::TokenStream {
+    let args = params
         .iter()
-        .map(|option| {
-            let name = match &option.meta.name {
-                None => option.name.to_string(),
-                Some(x) => x.clone(),
-            };
-            let (required, t) = parse_option_type(&option.t);
-            if required {
-                quote::quote! {
-                    #t::from_option(options.iter().find(|x| x.name == #name).cloned().unwrap().value).unwrap()
+        .map(|param| match param {
+            CommandParam::Option(option) => parse_option_value(option),
+            CommandParam::Options(ty) => quote::quote! {
+                <#ty as ::edgecord::application_command::CommandOptions>::from_options(&options, resolved)?
+            },
+        })
+        .collect::<Vec<_>>();
+    let resolved = if args.is_empty() {
+        quote::quote! {}
+    } else {
+        quote::quote! { let resolved = interaction.data.resolved.as_ref(); }
+    };
+    let checks = checks
+        .iter()
+        .map(|check| {
+            let name = quote::quote!(#check).to_string().replace(' ', "");
+            quote::quote! {
+                if !#check(&ctx).await? {
+                    return Err(::edgecord::Error::CheckFailed(#name.to_string()));
                 }
-            } else {
+            }
+        })
+        .collect::<Vec<_>>();
+    quote::quote! {
+        ::std::rc::Rc::new(move |ctx, interaction, options| Box::pin(async move {
+            #( #checks )*
+            #resolved
+            ::edgecord::handler::IntoResponseResult::into_response_result(
+                inner(ctx, #( #args, )*).await
+            )
+        }))
+    }
+}
+
+/**
+Generate match arms that call the autocomplete function of the focused option.
+**/
+pub(crate) fn autocomplete_arms(options: &[&CommandOption]) -> Vec<proc_macro2::TokenStream> {
+    options
+        .iter()
+        .filter_map(|option| {
+            let name = option
+                .meta
+                .name
+                .clone()
+                .unwrap_or_else(|| option.name.to_string());
+            option.meta.autocomplete.as_ref().map(|path| {
                 quote::quote! {
-                    options.iter().find(|x| x.name == #name).cloned().map(|value| #t::from_option(value.value).unwrap())
+                    #name => #path(ctx, focused.value.unwrap_or_default(), options).await
                 }
-            }
+            })
+        })
+        .collect()
+}
+
+fn parse_autocomplete(params: &[CommandParam]) -> proc_macro2::TokenStream {
+    let options = params
+        .iter()
+        .filter_map(|param| match param {
+            CommandParam::Option(option) => Some(option),
+            CommandParam::Options(_) => None,
         })
         .collect::<Vec<_>>();
+    let mut arms = autocomplete_arms(&options);
+    arms.extend(params.iter().filter_map(|param| match param {
+        CommandParam::Options(ty) => Some(quote::quote! {
+            name if <#ty as ::edgecord::application_command::CommandOptions>::has_autocomplete(name) =>
+                <#ty as ::edgecord::application_command::CommandOptions>::autocomplete(ctx, focused, options).await
+        }),
+        CommandParam::Option(_) => None,
+    }));
+    if arms.is_empty() {
+        return quote::quote! {None};
+    }
     quote::quote! {
-        ::std::rc::Rc::new(move |ctx, interaction, options| Box::pin(inner(ctx, #( #args, )*)))
+        Some(::std::rc::Rc::new(move |ctx, focused, options| Box::pin(async move {
+            match focused.name.as_str() {
+                #( #arms, )*
+                _ => vec![],
+            }
+        })))
     }
 }
 
-fn parse_option_meta(option: &CommandOption) -> proc_macro2::TokenStream {
-    let i18n_names = parse_i18n(option.meta.i18n_names.clone());
-    let i18n_descriptions = parse_i18n(option.meta.i18n_descriptions.clone());
+pub(crate) fn parse_option_meta(option: &CommandOption) -> proc_macro2::TokenStream {
+    let i18n_names = &option.i18n_names;
+    let i18n_descriptions = &option.i18n_descriptions;
     let name = option
         .meta
         .name
@@ -164,6 +397,9 @@ fn parse_option_meta(option: &CommandOption) -> proc_macro2::TokenStream {
     let (required, ty) = parse_option_type(&option.t);
     let min_value = parse_range_value(&option.meta.min_value);
     let max_value = parse_range_value(&option.meta.max_value);
+    let min_length = parse_length(option.meta.min_length);
+    let max_length = parse_length(option.meta.max_length);
+    let autocomplete = option.meta.autocomplete.is_some();
     let channel_types = {
         if let Some(x) = option.meta.channel_types.clone().map(|x| x.to_vec_token()) {
             quote::quote!(Some(#x))
@@ -174,16 +410,19 @@ fn parse_option_meta(option: &CommandOption) -> proc_macro2::TokenStream {
 
     quote::quote! {
         ::edgecord::application_command::CommandOption {
-            option_type: #ty::get_option_type(),
+            option_type: <#ty>::get_option_type(),
             name: #name.to_string(),
             description: #description.to_string(),
             i18n_names: #i18n_names,
             i18n_descriptions: #i18n_descriptions,
-            choices: #ty::choices(),
+            choices: <#ty>::choices(),
             required: #required,
             min_value: #min_value,
             max_value: #max_value,
-            channel_types: #channel_types
+            min_length: #min_length,
+            max_length: #max_length,
+            channel_types: #channel_types,
+            autocomplete: #autocomplete,
         }
     }
 }
@@ -198,7 +437,14 @@ fn parse_range_value(value: &Option<syn::Lit>) -> proc_macro2::TokenStream {
     }
 }
 
-fn parse_option_type(ty: &syn::Type) -> (bool, syn::Type) {
+fn parse_length(value: Option<u16>) -> proc_macro2::TokenStream {
+    match value {
+        None => quote::quote! {None},
+        Some(x) => quote::quote! {Some(#x)},
+    }
+}
+
+pub(crate) fn parse_option_type(ty: &syn::Type) -> (bool, syn::Type) {
     fn path_is_option(path: &syn::Path) -> bool {
         path.leading_colon.is_none()
             && path.segments.len() == 1
@@ -1,5 +1,7 @@
+use crate::cooldown::{parse_cooldown, CooldownMeta};
 use crate::permission::PermissionFlagBits;
-use crate::utils::parse_i18n;
+use crate::utils::{arg_span, parse_dm_permission, parse_localizations};
+use crate::validate::{validate_description, validate_name};
 use proc_macro::TokenStream;
 use syn::spanned::Spanned;
 
@@ -9,11 +11,16 @@ pub(crate) struct CommandGroupMeta {
     pub description: String,
     pub i18n_names: Option<syn::Path>,
     pub i18n_descriptions: Option<syn::Path>,
+    pub i18n: Option<String>,
     pub default_permissions: Option<PermissionFlagBits>,
+    pub dm_permission: Option<bool>,
+    pub nsfw: Option<bool>,
+    pub cooldown: Option<CooldownMeta>,
 }
 
 pub(crate) fn parse_command_group(
     args: CommandGroupMeta,
+    nested: &[syn::NestedMeta],
     mut func: syn::ItemFn,
 ) -> Result<TokenStream, darling::Error> {
     if func.sig.asyncness.is_some() {
@@ -22,13 +29,35 @@ pub(crate) fn parse_command_group(
         );
     }
 
+    let name_span = match args.name {
+        Some(_) => arg_span(nested, "name"),
+        None => func.sig.ident.span(),
+    };
     let command_group_name = args.name.unwrap_or_else(|| func.sig.ident.to_string());
 
     let description = args.description;
-    let i18n_names = parse_i18n(args.i18n_names);
-    let i18n_descriptions = parse_i18n(args.i18n_descriptions);
+    validate_name(&command_group_name, name_span)?;
+    validate_description(&description, arg_span(nested, "description"))?;
+    let i18n_span = |field| arg_span(nested, if args.i18n.is_some() { "i18n" } else { field });
+    let i18n_names = parse_localizations(
+        args.i18n_names,
+        args.i18n.as_ref(),
+        "name",
+        i18n_span("i18n_names"),
+        |x| validate_name(x, i18n_span("i18n_names")),
+    )?;
+    let i18n_descriptions = parse_localizations(
+        args.i18n_descriptions,
+        args.i18n.as_ref(),
+        "description",
+        i18n_span("i18n_descriptions"),
+        |x| validate_description(x, i18n_span("i18n_descriptions")),
+    )?;
     let function_name = std::mem::replace(&mut func.sig.ident, syn::parse_quote! { inner });
     let visibility = &func.vis;
+    let dm_permission = parse_dm_permission(args.dm_permission);
+    let nsfw = args.nsfw.unwrap_or(false);
+    let cooldown = parse_cooldown(args.cooldown, arg_span(nested, "cooldown"))?;
     let default_permissions = {
         match args.default_permissions {
             None => quote::quote! {None},
@@ -50,6 +79,9 @@ pub(crate) fn parse_command_group(
                 i18n_descriptions: #i18n_descriptions,
                 commands: inner(),
                 default_permissions: #default_permissions,
+                dm_permission: #dm_permission,
+                nsfw: #nsfw,
+                cooldown: #cooldown,
             }
         }
     }))
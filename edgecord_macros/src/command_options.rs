@@ -0,0 +1,111 @@
+use crate::command::{
+    autocomplete_arms, parse_option_meta, parse_option_value, CommandOption, OptionMetaWrapped,
+};
+use crate::validate::{validate_option, validate_options};
+use darling::FromMeta;
+use proc_macro::TokenStream;
+use syn::spanned::Spanned;
+use syn::Data;
+
+pub fn expand_derive_command_options(input: syn::DeriveInput) -> Result<TokenStream, syn::Error> {
+    let fields = match input.clone().data {
+        Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields.named,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "command options must be struct with named fields",
+            ))
+        }
+    };
+    let mut options = vec![];
+    for field in fields {
+        let span = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("option"))
+            .map(|attr| attr.span())
+            .unwrap_or_else(|| field.span());
+        let attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("option"))
+            .map(|attr| attr.parse_meta().map(syn::NestedMeta::Meta))
+            .collect::<Result<Vec<_>, _>>()?;
+        let meta = OptionMetaWrapped::from_list(&attrs)?.option;
+        let option =
+            CommandOption::new(field.ident.clone().unwrap(), field.ty.clone(), meta, span)?;
+        validate_option(&option)?;
+        options.push(option);
+    }
+    validate_options(&options.iter().collect::<Vec<_>>(), input.span())?;
+
+    let struct_name = &input.ident;
+    let schemas = options.iter().map(parse_option_meta).collect::<Vec<_>>();
+    let parsers = options
+        .iter()
+        .map(|option| {
+            let ident = &option.name;
+            let value = parse_option_value(option);
+            quote::quote! { #ident: #value }
+        })
+        .collect::<Vec<_>>();
+    let autocomplete_names = options
+        .iter()
+        .filter(|option| option.meta.autocomplete.is_some())
+        .map(|option| {
+            option
+                .meta
+                .name
+                .clone()
+                .unwrap_or_else(|| option.name.to_string())
+        })
+        .collect::<Vec<_>>();
+    let arms = autocomplete_arms(&options.iter().collect::<Vec<_>>());
+    let autocomplete = if arms.is_empty() {
+        quote::quote! {}
+    } else {
+        quote::quote! {
+            fn has_autocomplete(name: &str) -> bool {
+                [#( #autocomplete_names, )*].contains(&name)
+            }
+
+            fn autocomplete(
+                ctx: ::edgecord::application_command::AutocompleteContext,
+                focused: ::edgecord::model::application::interaction::application_command_autocomplete::ApplicationCommandAutocompleteDataOption,
+                options: Vec<::edgecord::model::application::interaction::application_command_autocomplete::ApplicationCommandAutocompleteDataOption>,
+            ) -> ::std::pin::Pin<Box<dyn ::std::future::Future<Output = Vec<::edgecord::application_command::Choice>>>> {
+                Box::pin(async move {
+                    match focused.name.as_str() {
+                        #( #arms, )*
+                        _ => vec![],
+                    }
+                })
+            }
+        }
+    };
+
+    Ok(TokenStream::from(quote::quote! {
+        impl ::edgecord::application_command::CommandOptions for #struct_name {
+            fn options() -> Vec<::edgecord::application_command::CommandOption> {
+                use ::edgecord::application_command::option::FromCommandOptionValue;
+                vec![#( #schemas, )*]
+            }
+
+            #[allow(unused_variables)]
+            fn from_options(
+                options: &[::edgecord::model::application::interaction::application_command::CommandDataOption],
+                resolved: Option<&::edgecord::model::application::interaction::application_command::CommandInteractionDataResolved>,
+            ) -> ::edgecord::Result<Self> {
+                use ::edgecord::application_command::option::FromCommandOptionValue;
+                Ok(Self {
+                    #( #parsers, )*
+                })
+            }
+
+            #autocomplete
+        }
+    }))
+}
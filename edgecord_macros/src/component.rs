@@ -0,0 +1,104 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+#[derive(Debug, darling::FromMeta)]
+pub(crate) struct ComponentMeta {
+    pub custom_id: Option<String>,
+    pub prefix: Option<String>,
+}
+
+pub(crate) fn parse_component(
+    args: ComponentMeta,
+    mut func: syn::ItemFn,
+) -> Result<TokenStream, darling::Error> {
+    if func.sig.asyncness.is_none() {
+        return Err(syn::Error::new(func.sig.span(), "component function must be async").into());
+    }
+    if func.sig.inputs.len() != 1 {
+        return Err(syn::Error::new(
+            func.sig.inputs.span(),
+            "component function must take only ComponentContext",
+        )
+        .into());
+    }
+
+    let custom_id = match (args.custom_id, args.prefix) {
+        (Some(id), None) if id.contains('{') => {
+            validate_pattern(&id)?;
+            quote::quote! {::edgecord::message_component::CustomId::Pattern(#id.to_string())}
+        }
+        (Some(id), None) => {
+            quote::quote! {::edgecord::message_component::CustomId::Exact(#id.to_string())}
+        }
+        (None, Some(prefix)) => {
+            quote::quote! {::edgecord::message_component::CustomId::Prefix(#prefix.to_string())}
+        }
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "component needs either `custom_id` or `prefix`",
+            )
+            .into())
+        }
+    };
+
+    let function_name = std::mem::replace(&mut func.sig.ident, syn::parse_quote! { inner });
+    let visibility = &func.vis;
+
+    Ok(TokenStream::from(quote::quote! {
+        #visibility fn #function_name() -> ::edgecord::message_component::Component {
+            #func
+
+            ::edgecord::message_component::Component {
+                custom_id: #custom_id,
+                action: ::std::rc::Rc::new(move |ctx| Box::pin(async move {
+                    ::edgecord::handler::IntoResponseResult::into_response_result(inner(ctx).await)
+                })),
+            }
+        }
+    }))
+}
+
+fn validate_pattern(pattern: &str) -> Result<(), darling::Error> {
+    if pattern.len() > 100 {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "custom_id length is longer than limit ({} > 100)",
+                pattern.len()
+            ),
+        )
+        .into());
+    }
+    let mut opened = false;
+    let mut last_is_placeholder = false;
+    for c in pattern.chars() {
+        match c {
+            '{' if opened => {
+                return Err(syn::Error::new(Span::call_site(), "nested `{` in custom_id").into())
+            }
+            '{' if last_is_placeholder => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "placeholders in custom_id must be separated by text",
+                )
+                .into())
+            }
+            '{' => opened = true,
+            '}' if !opened => {
+                return Err(syn::Error::new(Span::call_site(), "unmatched `}` in custom_id").into())
+            }
+            '}' => {
+                opened = false;
+                last_is_placeholder = true;
+            }
+            _ if !opened => last_is_placeholder = false,
+            _ => {}
+        }
+    }
+    if opened {
+        return Err(syn::Error::new(Span::call_site(), "unclosed `{` in custom_id").into());
+    }
+    Ok(())
+}
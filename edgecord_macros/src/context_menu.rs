@@ -0,0 +1,110 @@
+use crate::permission::PermissionFlagBits;
+use crate::utils::{parse_dm_permission, parse_localizations};
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+#[derive(Debug, darling::FromMeta)]
+pub(crate) struct ContextMenuMeta {
+    pub name: Option<String>,
+    pub i18n_names: Option<syn::Path>,
+    pub i18n: Option<String>,
+    pub default_permissions: Option<PermissionFlagBits>,
+    pub dm_permission: Option<bool>,
+    pub nsfw: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ContextMenuType {
+    User,
+    Message,
+}
+
+fn validate_context_menu_name(name: &str, span: Span) -> Result<(), syn::Error> {
+    if name.is_empty() || name.chars().count() > 32 {
+        return Err(syn::Error::new(
+            span,
+            "context menu command name length must be between 1 and 32",
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_context_menu(
+    args: ContextMenuMeta,
+    mut func: syn::ItemFn,
+    kind: ContextMenuType,
+) -> Result<TokenStream, darling::Error> {
+    if func.sig.asyncness.is_none() {
+        return Err(syn::Error::new(func.sig.span(), "command function must be async").into());
+    }
+    let (command_type, context, context_name) = match kind {
+        ContextMenuType::User => (
+            quote::quote! {User},
+            quote::quote! {::edgecord::application_command::UserCommandContext},
+            "UserCommandContext",
+        ),
+        ContextMenuType::Message => (
+            quote::quote! {Message},
+            quote::quote! {::edgecord::application_command::MessageCommandContext},
+            "MessageCommandContext",
+        ),
+    };
+    if func.sig.inputs.len() != 1 {
+        return Err(syn::Error::new(
+            func.sig.inputs.span(),
+            format!(
+                "context menu command function must take only {}",
+                context_name
+            ),
+        )
+        .into());
+    }
+
+    let command_name = args.name.unwrap_or_else(|| func.sig.ident.to_string());
+    validate_context_menu_name(&command_name, func.sig.ident.span())?;
+    let i18n_names = parse_localizations(
+        args.i18n_names,
+        args.i18n.as_ref(),
+        "name",
+        Span::call_site(),
+        |x| validate_context_menu_name(x, Span::call_site()),
+    )?;
+    let function_name = std::mem::replace(&mut func.sig.ident, syn::parse_quote! { inner });
+    let visibility = &func.vis;
+    let dm_permission = parse_dm_permission(args.dm_permission);
+    let nsfw = args.nsfw.unwrap_or(false);
+    let default_permissions = {
+        match args.default_permissions {
+            None => quote::quote! {None},
+            Some(x) => {
+                let y = x.bits().bits();
+                quote::quote! {Some(#y)}
+            }
+        }
+    };
+
+    Ok(TokenStream::from(quote::quote! {
+        #visibility fn #function_name() -> ::edgecord::application_command::Command {
+            #func
+
+            ::edgecord::application_command::Command {
+                command_type: ::edgecord::model::application::command::CommandType::#command_type,
+                name: #command_name.to_string(),
+                description: String::new(),
+                i18n_names: #i18n_names,
+                i18n_descriptions: None,
+                default_permissions: #default_permissions,
+                dm_permission: #dm_permission,
+                nsfw: #nsfw,
+                options: vec![],
+                action: ::std::rc::Rc::new(move |ctx, _interaction, _options| Box::pin(async move {
+                    ::edgecord::handler::IntoResponseResult::into_response_result(
+                        inner(#context::new(ctx)).await
+                    )
+                })),
+                autocomplete: None,
+            }
+        }
+    }))
+}
@@ -0,0 +1,91 @@
+use proc_macro2::Span;
+
+/**
+`cooldown(rate = 1, per = "30s", scope = "user")` argument.
+**/
+#[derive(Debug, darling::FromMeta)]
+pub(crate) struct CooldownMeta {
+    pub rate: Option<u32>,
+    pub per: String,
+    pub scope: Option<String>,
+}
+
+/**
+Parse the duration like `30s`, `5m`, `1h` or `1d` into seconds.
+**/
+pub(crate) fn parse_duration(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let unit = match value.chars().last()? {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    value[..value.len() - 1]
+        .parse::<u64>()
+        .ok()
+        .map(|x| x * unit)
+}
+
+pub(crate) fn parse_cooldown(
+    meta: Option<CooldownMeta>,
+    span: Span,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let meta = match meta {
+        Some(x) => x,
+        None => return Ok(quote::quote! {None}),
+    };
+    let rate = meta.rate.unwrap_or(1);
+    if rate == 0 {
+        return Err(syn::Error::new(span, "cooldown rate must be at least 1"));
+    }
+    let per = match parse_duration(&meta.per) {
+        Some(x) if x > 0 => x,
+        _ => {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "invalid cooldown duration `{}`, use like `30s`, `5m`, `1h` or `1d`",
+                    meta.per
+                ),
+            ))
+        }
+    };
+    let scope = match meta.scope.as_deref().unwrap_or("user") {
+        "user" => quote::quote! {User},
+        "guild" => quote::quote! {Guild},
+        "channel" => quote::quote! {Channel},
+        "global" => quote::quote! {Global},
+        x => {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "unknown cooldown scope `{}`, use `user`, `guild`, `channel` or `global`",
+                    x
+                ),
+            ))
+        }
+    };
+    Ok(quote::quote! {
+        Some(::edgecord::cooldown::Cooldown {
+            rate: #rate,
+            per: ::std::time::Duration::from_secs(#per),
+            scope: ::edgecord::cooldown::CooldownScope::#scope,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Some(30));
+        assert_eq!(parse_duration("5m"), Some(300));
+        assert_eq!(parse_duration("1h"), Some(3600));
+        assert_eq!(parse_duration("30"), None);
+        assert_eq!(parse_duration("s"), None);
+    }
+}
@@ -0,0 +1,169 @@
+use edgelord::i18n::{lookup, LOCALES};
+use proc_macro2::Span;
+use serde_json::Value;
+use std::path::PathBuf;
+
+const DEFAULT_DIR: &str = "i18n";
+const DEFAULT_FALLBACK: &str = "en-US";
+
+pub(crate) struct CatalogFile {
+    pub locale: syn::Ident,
+    pub path: PathBuf,
+    pub value: Value,
+}
+
+/**
+The translation catalog of the crate.
+
+The catalog is the JSON files named by the locale code (like `ja.json`) in `i18n` directory of the crate.
+The directory and the fallback locale can be changed by `EDGECORD_I18N_DIR` and `EDGECORD_I18N_FALLBACK` environment variables.
+**/
+pub(crate) struct Catalog {
+    pub fallback: syn::Ident,
+    pub fallback_code: String,
+    pub files: Vec<CatalogFile>,
+}
+
+fn locale_variant(code: &str) -> Option<syn::Ident> {
+    LOCALES
+        .iter()
+        .find(|(x, _)| *x == code)
+        .map(|(_, variant)| syn::Ident::new(variant, Span::call_site()))
+}
+
+impl Catalog {
+    pub fn load(span: Span) -> Result<Self, syn::Error> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+        let dir = PathBuf::from(manifest_dir)
+            .join(std::env::var("EDGECORD_I18N_DIR").unwrap_or_else(|_| DEFAULT_DIR.to_string()));
+        let fallback_code = std::env::var("EDGECORD_I18N_FALLBACK")
+            .unwrap_or_else(|_| DEFAULT_FALLBACK.to_string());
+        let fallback = locale_variant(&fallback_code).ok_or_else(|| {
+            syn::Error::new(span, format!("unknown fallback locale `{}`", fallback_code))
+        })?;
+
+        let entries = std::fs::read_dir(&dir).map_err(|e| {
+            syn::Error::new(
+                span,
+                format!("failed to read i18n catalog `{}`: {}", dir.display(), e),
+            )
+        })?;
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|x| x.path()))
+            .filter(|path| path.extension().map(|x| x == "json").unwrap_or(false))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let mut files = vec![];
+        for path in paths {
+            let code = path
+                .file_stem()
+                .and_then(|x| x.to_str())
+                .unwrap_or_default()
+                .to_string();
+            let locale = locale_variant(&code).ok_or_else(|| {
+                syn::Error::new(
+                    span,
+                    format!("unknown locale `{}` in `{}`", code, path.display()),
+                )
+            })?;
+            let value = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|x| serde_json::from_str::<Value>(&x).map_err(|e| e.to_string()))
+                .map_err(|e| {
+                    syn::Error::new(span, format!("failed to load `{}`: {}", path.display(), e))
+                })?;
+            files.push(CatalogFile {
+                locale,
+                path,
+                value,
+            });
+        }
+        if !files.iter().any(|x| x.locale == fallback) {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "fallback locale file `{}.json` is not found in `{}`",
+                    fallback_code,
+                    dir.display()
+                ),
+            ));
+        }
+        Ok(Self {
+            fallback,
+            fallback_code,
+            files,
+        })
+    }
+
+    /**
+    Get the translations of `{key}.{field}`. It is an error if the fallback locale doesn't have the key.
+    **/
+    pub fn localizations(
+        &self,
+        key: &str,
+        field: &str,
+        span: Span,
+    ) -> Result<Vec<(syn::Ident, String)>, syn::Error> {
+        let key = format!("{}.{}", key, field);
+        let entries = self
+            .files
+            .iter()
+            .filter_map(|file| {
+                lookup(&file.value, &key).map(|x| (file.locale.clone(), x.to_string()))
+            })
+            .collect::<Vec<_>>();
+        if !entries.iter().any(|(locale, _)| *locale == self.fallback) {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "key `{}` is not found in the fallback locale `{}`",
+                    key, self.fallback_code
+                ),
+            ));
+        }
+        Ok(entries)
+    }
+
+    /**
+    Generate `include_bytes!` of the catalog files so that the crate is rebuilt when they are changed.
+    **/
+    pub fn track(&self) -> proc_macro2::TokenStream {
+        let paths = self
+            .files
+            .iter()
+            .map(|file| file.path.display().to_string())
+            .collect::<Vec<_>>();
+        quote::quote! {
+            #( const _: &[u8] = include_bytes!(#paths); )*
+        }
+    }
+}
+
+/**
+Generate the expression of `catalog!` macro.
+**/
+pub(crate) fn expand_catalog(span: Span) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let catalog = Catalog::load(span)?;
+    let fallback = &catalog.fallback;
+    let files = catalog
+        .files
+        .iter()
+        .map(|file| {
+            let locale = &file.locale;
+            let path = file.path.display().to_string();
+            quote::quote! {
+                catalog.add_json(::edgecord::application_command::i18n::Locales::#locale, include_str!(#path));
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(quote::quote! {
+        {
+            let mut catalog = ::edgecord::application_command::i18n::Catalog::new(
+                ::edgecord::application_command::i18n::Locales::#fallback,
+            );
+            #( #files )*
+            catalog
+        }
+    })
+}
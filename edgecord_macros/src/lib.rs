@@ -2,12 +2,20 @@ mod channel_type;
 mod choice;
 mod command;
 mod command_group;
+mod command_options;
+mod component;
+mod context_menu;
+mod cooldown;
+mod i18n;
+mod modal;
 mod permission;
 mod utils;
 mod validate;
 
 use crate::command::{parse_command, CommandMeta};
 use crate::command_group::{parse_command_group, CommandGroupMeta};
+use crate::component::{parse_component, ComponentMeta};
+use crate::context_menu::{parse_context_menu, ContextMenuMeta, ContextMenuType};
 #[allow(unused_imports)]
 use darling::FromMeta as _;
 use proc_macro::TokenStream;
@@ -16,7 +24,8 @@ use syn::{parse_macro_input, DeriveInput};
 /**
 This macro transforms a function into edgecord slash command.
 
-The function must return edgecord::InteractionResponse.
+The function must return edgecord::InteractionResponse or `Result<InteractionResponse, E>`.
+If it returns an error, the error is passed to the error handler registered by `CommandHandlerBuilder::on_error`.
 
 # Macro Arguments
 
@@ -24,7 +33,11 @@ The function must return edgecord::InteractionResponse.
 - `description`: The description of the slash command(or sub command). Required for arguments.
 - `i18n_names`: A function that returns HashMap<Locales, String> of localization command(or subcommand) name.
 - `i18n_descriptions`: A function that returns HashMap<Locales, String> for localization command description.
+- `i18n`: The key of the catalog. `{key}.name` and `{key}.description` are used instead of `i18n_names` and `i18n_descriptions`. See `catalog!` macro.
 - `default_permissions(send_messages, ..)`: The permissions that a member has to have when he uses this command. You can add plural permissions like `default_permissions(send_messages, manage_roles)`. The permission name is the snake_case enum member of edgecord::model::guild::Permissions. If this command is used as a subcommand, this field is ignored.
+- `dm_permission`: Whether the command is available in DMs. It is available only for global commands.
+- `nsfw`: Mark the command as age-restricted.
+- `cooldown(rate = 1, per = "30s", scope = "user")`: Allow `rate` invocations every `per` (`s`, `m`, `h` or `d`). The scope is `user`, `guild`, `channel` or `global`, and the default is `user`. See `edgecord::cooldown`.
 
 # Function Parameter Attribute Arguments
 
@@ -35,6 +48,8 @@ From second parameters onwards, they are used as slashcommand options.
 
 The option type must be in `[String, i64, f64, bool, Id<ChannelMaker>, Id<RoleMaker>, Id<UserMaker>, Id<GenericMaker>, Id<AttachmentMaker> or Choiceable]`.
 
+You can also use the resolved objects `User`, `(User, Option<InteractionMember>)`, `Role`, `InteractionChannel` and `Attachment` instead of ids.
+
 The option is required in default. You can use Option<T> for option type. If you use Option, the option will be not required.
 
 You can add these arguments by using `option(...)` attribute.
@@ -43,9 +58,34 @@ You can add these arguments by using `option(...)` attribute.
 - `description`: The description of the option. Required for arguments.
 - `i18n_names`: A function that returns HashMap<Locales, String> of localization option name.
 - `i18n_descriptions`: A function that returns HashMap<Locales, String> of localization option description.
-- `min_value`: The minimum value permitted. It is available if the option is i64 or u64.
-- `max_value`: The maximum value permitted. It is available if the option is i64 or u64.
-- `channel_types(guild_text, ..)`: The channel types for the option. The permission name is the snake_case enum member of ::edgecord::models::channel::ChannelType.
+- `i18n`: The key of the catalog. `{key}.name` and `{key}.description` are used instead of `i18n_names` and `i18n_descriptions`. See `catalog!` macro.
+- `min_value`: The minimum value permitted. It is available if the option is i64 or f64.
+- `max_value`: The maximum value permitted. It is available if the option is i64 or f64.
+- `min_length`: The minimum length permitted (0 to 6000). It is available if the option is String.
+- `max_length`: The maximum length permitted (1 to 6000). It is available if the option is String.
+- `autocomplete`: An async function that returns choices for the option while the user is typing. It receives `AutocompleteContext`, the partially typed value as String and the other filled options, and returns up to 25 `Choice`s.
+- `channel_types(guild_text, ..)`: The channel types for the option. The permission name is the snake_case enum member of ::edgecord::models::channel::ChannelType. It is available if the option is Id<ChannelMarker> or InteractionChannel.
+
+You can also take a struct deriving `CommandOptions` with `#[options]` attribute. Its fields are expanded into the options of the command.
+The expanded options are checked by `CommandHandlerBuilder::build`: up to 25 options, unique names and required options first.
+
+# Checks
+
+`#[check(path)]` attributes after `#[command]` add guards to the command.
+The check is an async function that takes `&ChatInputCommandContext` and returns `edgecord::Result<bool>`.
+The checks run in order before the options are parsed. If a check returns false, `Error::CheckFailed` is passed to the error handler.
+
+```ignore
+async fn owner_only(ctx: &ChatInputCommandContext) -> edgecord::Result<bool> {
+    Ok(ctx.interaction.author_id().map(|x| x.get()) == Some(OWNER_ID))
+}
+
+#[command(description = "shutdown")]
+#[check(owner_only)]
+async fn shutdown(ctx: ChatInputCommandContext) -> InteractionResponse {
+    ctx.message(|msg| msg.content("bye"))
+}
+```
 
 # Examples
 
@@ -66,15 +106,15 @@ async fn echo(
 **/
 #[proc_macro_attribute]
 pub fn command(args: TokenStream, func: TokenStream) -> TokenStream {
-    let args = syn::parse_macro_input!(args as Vec<syn::NestedMeta>);
-    let args = match <CommandMeta as darling::FromMeta>::from_list(&args) {
+    let nested = syn::parse_macro_input!(args as Vec<syn::NestedMeta>);
+    let args = match <CommandMeta as darling::FromMeta>::from_list(&nested) {
         Ok(x) => x,
         Err(e) => return e.write_errors().into(),
     };
 
     let function = syn::parse_macro_input!(func as syn::ItemFn);
 
-    match parse_command(args, function) {
+    match parse_command(args, &nested, function) {
         Ok(stream) => stream,
         Err(e) => e.write_errors().into(),
     }
@@ -86,6 +126,7 @@ This macro transforms a function into edgecord slash command group or slash comm
 The function must return Vec<edgecord::command::SubCommand>.
 
 You can return either SubCommand::Group or SubCommand::Command, but you can't return SubCommand::Group if the parent group is in other group.
+The subcommands are made at runtime, so the nesting is checked by `CommandHandlerBuilder::build`.
 
 # Macro Arguments
 
@@ -93,7 +134,11 @@ You can return either SubCommand::Group or SubCommand::Command, but you can't re
 - `description`: The description of the group. Required for arguments.
 - `i18n_names`: A function that returns HashMap<Locales, String> of localization group name.
 - `i18n_descriptions`: A function that returns HashMap<Locales, String> of localization group description.
+- `i18n`: The key of the catalog. `{key}.name` and `{key}.description` are used instead of `i18n_names` and `i18n_descriptions`. See `catalog!` macro.
 - `default_permissions`: The permissions that a member has to have when he uses this command. You can add plural permissions like `default_permissions(send_messages, manage_roles)`. The permission name is the snake_case enum member of `edgecord::model::guild::Permissions`. If this command is used as a subcommand group, this field is ignored.
+- `dm_permission`: Whether the command is available in DMs. It is available only for global commands.
+- `nsfw`: Mark the command as age-restricted.
+- `cooldown(rate = 1, per = "30s", scope = "user")`: The cooldown shared by all subcommands of the group. See `command` macro.
 
 ```ignore
 // This group has a SubCommand::Group, so it has to be command.
@@ -106,15 +151,125 @@ fn animal_group() -> Vec<SubCommand> {
 **/
 #[proc_macro_attribute]
 pub fn group(args: TokenStream, func: TokenStream) -> TokenStream {
+    let nested = syn::parse_macro_input!(args as Vec<syn::NestedMeta>);
+    let args = match <CommandGroupMeta as darling::FromMeta>::from_list(&nested) {
+        Ok(x) => x,
+        Err(e) => return e.write_errors().into(),
+    };
+
+    let function = syn::parse_macro_input!(func as syn::ItemFn);
+
+    match parse_command_group(args, &nested, function) {
+        Ok(stream) => stream,
+        Err(e) => e.write_errors().into(),
+    }
+}
+
+/**
+This macro transforms a function into edgecord user command (right click on a user > Apps).
+
+The function must be async, take only `UserCommandContext` and return edgecord::InteractionResponse or `Result<InteractionResponse, E>`.
+The target user is available by `ctx.target_user()` and `ctx.target_member()`.
+
+# Macro Arguments
+
+- `name`: The command name. It can contain spaces and capital letters.
+- `i18n_names`: A function that returns HashMap<Locales, String> of localization command name.
+- `i18n`: The key of the catalog. `{key}.name` is used instead of `i18n_names`. See `catalog!` macro.
+- `default_permissions(send_messages, ..)`: The permissions that a member has to have when he uses this command.
+- `dm_permission`: Whether the command is available in DMs. It is available only for global commands.
+- `nsfw`: Mark the command as age-restricted.
+
+```ignore
+#[user_command(name = "Show Avatar")]
+async fn show_avatar(ctx: UserCommandContext) -> InteractionResponse {
+    let name = ctx.target_user().map(|x| x.name.clone()).unwrap_or_default();
+    ctx.message(|msg| msg.content(name))
+}
+```
+
+**/
+#[proc_macro_attribute]
+pub fn user_command(args: TokenStream, func: TokenStream) -> TokenStream {
+    context_menu(args, func, ContextMenuType::User)
+}
+
+/**
+This macro transforms a function into edgecord message command (right click on a message > Apps).
+
+The function must be async, take only `MessageCommandContext` and return edgecord::InteractionResponse or `Result<InteractionResponse, E>`.
+The target message is available by `ctx.target_message()`.
+
+# Macro Arguments
+
+- `name`: The command name. It can contain spaces and capital letters.
+- `i18n_names`: A function that returns HashMap<Locales, String> of localization command name.
+- `i18n`: The key of the catalog. `{key}.name` is used instead of `i18n_names`. See `catalog!` macro.
+- `default_permissions(send_messages, ..)`: The permissions that a member has to have when he uses this command.
+- `dm_permission`: Whether the command is available in DMs. It is available only for global commands.
+- `nsfw`: Mark the command as age-restricted.
+
+```ignore
+#[message_command(name = "Quote")]
+async fn quote(ctx: MessageCommandContext) -> InteractionResponse {
+    let content = ctx.target_message().map(|x| x.content.clone()).unwrap_or_default();
+    ctx.message(|msg| msg.content(format!("> {}", content)))
+}
+```
+
+**/
+#[proc_macro_attribute]
+pub fn message_command(args: TokenStream, func: TokenStream) -> TokenStream {
+    context_menu(args, func, ContextMenuType::Message)
+}
+
+fn context_menu(args: TokenStream, func: TokenStream, kind: ContextMenuType) -> TokenStream {
     let args = syn::parse_macro_input!(args as Vec<syn::NestedMeta>);
-    let args = match <CommandGroupMeta as darling::FromMeta>::from_list(&args) {
+    let args = match <ContextMenuMeta as darling::FromMeta>::from_list(&args) {
         Ok(x) => x,
         Err(e) => return e.write_errors().into(),
     };
 
     let function = syn::parse_macro_input!(func as syn::ItemFn);
 
-    match parse_command_group(args, function) {
+    match parse_context_menu(args, function, kind) {
+        Ok(stream) => stream,
+        Err(e) => e.write_errors().into(),
+    }
+}
+
+/**
+This macro transforms a function into edgecord message component (button or select menu) handler.
+
+The function must be async, take only `ComponentContext` and return edgecord::InteractionResponse or `Result<InteractionResponse, E>`.
+
+# Macro Arguments
+
+- `custom_id`: The custom id of the component. You can use placeholders like `"vote:{poll}"` to encode state in the id. The captured value is available by `ctx.param("poll")`.
+- `prefix`: Handle every component whose custom id starts with the prefix. You can't use it with `custom_id`.
+
+If a custom id matches plural handlers, the handler registered with the exact custom id is used.
+
+```ignore
+#[component(custom_id = "vote:{choice}")]
+async fn vote(ctx: ComponentContext) -> InteractionResponse {
+    let choice = ctx.param("choice").unwrap_or_default();
+    ctx.update_message(|msg| msg.content(format!("you voted for {}", choice)))
+}
+```
+
+**/
+#[proc_macro_attribute]
+pub fn component(args: TokenStream, func: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(args as Vec<syn::NestedMeta>);
+    let args = match <ComponentMeta as darling::FromMeta>::from_list(&args) {
+        Ok(x) => x,
+        Err(e) => return e.write_errors().into(),
+    };
+
+    let function = syn::parse_macro_input!(func as syn::ItemFn);
+
+    match parse_component(args, function) {
         Ok(stream) => stream,
         Err(e) => e.write_errors().into(),
     }
@@ -134,6 +289,7 @@ You can use the `choice(...)` macro in Choiceable.
 - `name`: The choice name.
 - `value`: The choice value. Required for arguments.
 - `i18n_names`: A function that returns HashMap<Locales, String> of localization choice name.
+- `i18n`: The key of the catalog. `{key}.name` is used instead of `i18n_names`. See `catalog!` macro.
 
 
 ```ignore
@@ -162,3 +318,119 @@ pub fn derive_choice(input: TokenStream) -> TokenStream {
 
     choice::expand_derive_choice(input).unwrap()
 }
+
+/**
+This is a macro that turns a struct into a modal form.
+
+Each field becomes a text input, and the struct is parsed from the submitted modal.
+The field type must implement `FromStr`. If you use Option<T>, the input will be not required.
+
+# Macro Arguments
+
+- `title`: The title of the modal. Required for arguments.
+- `custom_id`: The custom id of the modal. Default is the struct name.
+
+# Field Attribute Arguments
+
+You can add these arguments by using `input(...)` attribute.
+
+- `name`: The custom id of the text input. Default is the field name.
+- `label`: The label of the text input. Required for arguments.
+- `style`: `"short"` or `"paragraph"`. Default is `"short"`.
+- `min_length`: The minimum input length.
+- `max_length`: The maximum input length.
+- `placeholder`: The placeholder of the text input.
+- `value`: The pre-filled value of the text input.
+
+```ignore
+#[derive(edgecord::Modal)]
+#[modal(title = "Feedback")]
+struct Feedback {
+    #[input(label = "Your name", max_length = 32)]
+    name: String,
+    #[input(label = "Comment", style = "paragraph")]
+    comment: Option<String>,
+}
+
+#[command(description = "send feedback")]
+async fn feedback(ctx: ChatInputCommandContext) -> InteractionResponse {
+    ctx.modal::<Feedback>()
+}
+```
+**/
+#[proc_macro_derive(Modal, attributes(modal, input))]
+pub fn derive_modal(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match modal::expand_derive_modal(input) {
+        Ok(stream) => stream,
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/**
+This is a macro that turns a struct into a set of command options.
+
+Each field becomes an option of the command, in the order of the fields.
+Use the struct as a command parameter with `#[options]` attribute, and the same struct can be shared by plural commands.
+
+# Field Attribute Arguments
+
+Each field must have `option(...)` attribute. The arguments are the same as the option arguments of the `command` macro.
+
+```ignore
+#[derive(edgecord::CommandOptions)]
+struct Target {
+    #[option(description = "the target user")]
+    user: Id<UserMarker>,
+    #[option(description = "the reason")]
+    reason: Option<String>,
+}
+
+#[command(description = "ban a user")]
+async fn ban(ctx: ChatInputCommandContext, #[options] target: Target) -> InteractionResponse {
+    ctx.message(|msg| msg.content(format!("banned <@{}>", target.user)))
+}
+```
+**/
+#[proc_macro_derive(CommandOptions, attributes(option))]
+pub fn derive_command_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match command_options::expand_derive_command_options(input) {
+        Ok(stream) => stream,
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/**
+This macro embeds the translation catalog of the crate and returns `edgecord::application_command::i18n::Catalog`.
+
+The catalog is the JSON files named by the locale code (like `en-US.json` and `ja.json`) in `i18n` directory of the crate.
+The keys can be nested objects or dotted strings.
+You can change the directory and the fallback locale (default is `en-US`) by `EDGECORD_I18N_DIR` and `EDGECORD_I18N_FALLBACK` environment variables, for example in `.cargo/config.toml`.
+
+The same catalog is used by `i18n = "key"` argument of the macros. They read `{key}.name` and `{key}.description`,
+and it is a compile error if the fallback locale doesn't have the key.
+
+```ignore
+// i18n/ja.json: {"commands": {"ban": {"name": "バン", "description": "ユーザーをバンする"}}, "banned": "{user}をバンしました"}
+#[command(description = "ban a user", i18n = "commands.ban")]
+async fn ban(ctx: ChatInputCommandContext, #[option(description = "the user")] user: Id<UserMarker>) -> InteractionResponse {
+    let text = ctx.translate_with("banned", &[("user", format!("<@{}>", user).as_str())]);
+    ctx.message(|msg| msg.content(text))
+}
+
+let handler = InteractionHandler::builder()
+    .catalog(edgecord::catalog!())
+    .command(ban())
+    .build();
+```
+**/
+#[proc_macro]
+pub fn catalog(_input: TokenStream) -> TokenStream {
+    match i18n::expand_catalog(proc_macro2::Span::call_site()) {
+        Ok(stream) => stream.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,211 @@
+use crate::command::parse_option_type;
+use darling::FromMeta;
+use proc_macro::TokenStream;
+use syn::spanned::Spanned;
+use syn::Data;
+
+#[derive(Debug, darling::FromMeta)]
+pub(crate) struct ModalMeta {
+    pub custom_id: Option<String>,
+    pub title: String,
+}
+
+#[derive(Debug, darling::FromMeta)]
+pub(crate) struct ModalArgs {
+    pub modal: ModalMeta,
+}
+
+#[derive(Clone, Debug, darling::FromMeta, PartialEq)]
+#[darling(rename_all = "snake_case")]
+pub(crate) enum TextInputStyle {
+    Short,
+    Paragraph,
+}
+
+#[derive(Debug, darling::FromMeta)]
+pub(crate) struct InputMeta {
+    pub name: Option<String>,
+    pub label: String,
+    pub style: Option<TextInputStyle>,
+    pub min_length: Option<u16>,
+    pub max_length: Option<u16>,
+    pub placeholder: Option<String>,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, darling::FromMeta)]
+pub(crate) struct InputArgs {
+    pub input: InputMeta,
+}
+
+pub fn expand_derive_modal(mut input: syn::DeriveInput) -> Result<TokenStream, syn::Error> {
+    let fields = match input.clone().data {
+        Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields.named,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "modal must be struct with named fields",
+            ))
+        }
+    };
+    let struct_attrs = input
+        .attrs
+        .drain(..)
+        .filter(|attr| attr.path.is_ident("modal"))
+        .map(|attr| attr.parse_meta().map(syn::NestedMeta::Meta))
+        .collect::<Result<Vec<_>, _>>()?;
+    let meta = ModalArgs::from_list(&struct_attrs)?.modal;
+    let struct_name = &input.ident;
+    let custom_id = meta.custom_id.unwrap_or_else(|| struct_name.to_string());
+    let title = meta.title;
+
+    if title.chars().count() > 45 {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            format!(
+                "title length is longer than limit ({} > 45)",
+                title.chars().count()
+            ),
+        ));
+    }
+    if fields.len() > 5 {
+        return Err(syn::Error::new(
+            fields.span(),
+            format!("modal can't have more than 5 inputs ({})", fields.len()),
+        ));
+    }
+
+    let mut components = vec![];
+    let mut parsers = vec![];
+
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let attrs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("input"))
+            .map(|attr| attr.parse_meta().map(syn::NestedMeta::Meta))
+            .collect::<Result<Vec<_>, _>>()?;
+        let meta = InputArgs::from_list(&attrs)?.input;
+        validate_input(&meta, field.span())?;
+
+        let name = meta.name.clone().unwrap_or_else(|| ident.to_string());
+        let label = meta.label.clone();
+        let (required, ty) = parse_option_type(&field.ty);
+        let style = match meta.style {
+            Some(TextInputStyle::Paragraph) => quote::quote! {Paragraph},
+            _ => quote::quote! {Short},
+        };
+        let min_length = option_token(meta.min_length);
+        let max_length = option_token(meta.max_length);
+        let placeholder = option_token(meta.placeholder.map(|x| quote::quote! {#x.to_string()}));
+        let value = option_token(meta.value.map(|x| quote::quote! {#x.to_string()}));
+
+        components.push(quote::quote! {
+            Component::ActionRow(ActionRow {
+                components: vec![Component::TextInput(TextInput {
+                    custom_id: #name.to_string(),
+                    label: #label.to_string(),
+                    max_length: #max_length,
+                    min_length: #min_length,
+                    placeholder: #placeholder,
+                    required: Some(#required),
+                    style: TextInputStyle::#style,
+                    value: #value,
+                })],
+            })
+        });
+
+        let parse = quote::quote! {
+            <#ty as ::std::str::FromStr>::from_str(value)
+                .map_err(|_| ::edgecord::Error::InvalidModalField(#name.to_string()))?
+        };
+        parsers.push(if required {
+            quote::quote! {
+                #ident: {
+                    let value = ::edgecord::modal::get_value(data, #name)
+                        .filter(|x| !x.is_empty())
+                        .ok_or_else(|| ::edgecord::Error::InvalidModalField(#name.to_string()))?;
+                    #parse
+                }
+            }
+        } else {
+            quote::quote! {
+                #ident: match ::edgecord::modal::get_value(data, #name).filter(|x| !x.is_empty()) {
+                    Some(value) => Some(#parse),
+                    None => None,
+                }
+            }
+        });
+    }
+
+    Ok(TokenStream::from(quote::quote! {
+        impl ::edgecord::modal::Modal for #struct_name {
+            fn custom_id() -> String {
+                #custom_id.to_string()
+            }
+
+            fn title() -> String {
+                #title.to_string()
+            }
+
+            fn components() -> Vec<::edgecord::model::application::component::Component> {
+                use ::edgecord::model::application::component::{ActionRow, Component, TextInput};
+                use ::edgecord::model::application::component::text_input::TextInputStyle;
+                vec![ #( #components, )* ]
+            }
+
+            fn from_submit(
+                data: &::edgecord::model::application::interaction::modal::ModalInteractionData,
+            ) -> ::std::result::Result<Self, ::edgecord::Error> {
+                Ok(Self {
+                    #( #parsers, )*
+                })
+            }
+        }
+    }))
+}
+
+fn option_token<T: quote::ToTokens>(value: Option<T>) -> proc_macro2::TokenStream {
+    match value {
+        Some(x) => quote::quote! {Some(#x)},
+        None => quote::quote! {None},
+    }
+}
+
+fn validate_input(meta: &InputMeta, span: proc_macro2::Span) -> Result<(), syn::Error> {
+    if meta.label.is_empty() || meta.label.chars().count() > 45 {
+        return Err(syn::Error::new(
+            span,
+            "label length must be between 1 and 45",
+        ));
+    }
+    if let Some(min_length) = meta.min_length {
+        if min_length > 4000 {
+            return Err(syn::Error::new(
+                span,
+                "min_length must be between 0 and 4000",
+            ));
+        }
+    }
+    if let Some(max_length) = meta.max_length {
+        if max_length == 0 || max_length > 4000 {
+            return Err(syn::Error::new(
+                span,
+                "max_length must be between 1 and 4000",
+            ));
+        }
+    }
+    if let (Some(min_length), Some(max_length)) = (meta.min_length, meta.max_length) {
+        if min_length > max_length {
+            return Err(syn::Error::new(
+                span,
+                "min_length must be less than or equal to max_length",
+            ));
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,72 @@
+use crate::i18n::Catalog;
+use proc_macro2::Span;
+use syn::spanned::Spanned as _;
+
+/**
+The span of the macro argument with the key, such as `name` of `#[command(name = "...")]`.
+It falls back to the first argument, and to the call site if there are no arguments.
+**/
+pub fn arg_span(args: &[syn::NestedMeta], key: &str) -> Span {
+    args.iter()
+        .find(|arg| matches!(arg, syn::NestedMeta::Meta(meta) if meta.path().is_ident(key)))
+        .or_else(|| args.first())
+        .map(|arg| arg.span())
+        .unwrap_or_else(Span::call_site)
+}
+
 pub fn parse_i18n(path: Option<syn::Path>) -> proc_macro2::TokenStream {
     match path {
         Some(x) => quote::quote! {Some(#x())},
         None => quote::quote! {None},
     }
 }
+
+/**
+Generate the localizations from the function of `i18n_*` argument or the catalog key of `i18n` argument.
+`validate` is called for each translation.
+**/
+pub fn parse_localizations<F>(
+    path: Option<syn::Path>,
+    key: Option<&String>,
+    field: &str,
+    span: Span,
+    validate: F,
+) -> Result<proc_macro2::TokenStream, syn::Error>
+where
+    F: Fn(&str) -> Result<(), syn::Error>,
+{
+    let key = match (path, key) {
+        (Some(_), Some(_)) => {
+            return Err(syn::Error::new(
+                span,
+                format!("i18n and i18n_{}s can't be used together", field),
+            ))
+        }
+        (path, None) => return Ok(parse_i18n(path)),
+        (None, Some(key)) => key,
+    };
+    let catalog = Catalog::load(span)?;
+    let entries = catalog.localizations(key, field, span)?;
+    for (_, text) in &entries {
+        validate(text)?;
+    }
+    let track = catalog.track();
+    let entries = entries.iter().map(|(locale, text)| {
+        quote::quote! {
+            (::edgecord::application_command::i18n::Locales::#locale, #text.to_string())
+        }
+    });
+    Ok(quote::quote! {
+        {
+            #track
+            Some(::std::collections::HashMap::from([#( #entries, )*]))
+        }
+    })
+}
+
+pub fn parse_dm_permission(value: Option<bool>) -> proc_macro2::TokenStream {
+    match value {
+        Some(x) => quote::quote! {Some(#x)},
+        None => quote::quote! {None},
+    }
+}
@@ -1,20 +1,190 @@
-use crate::command::OptionMeta;
+use crate::command::{parse_option_type, CommandOption};
 use proc_macro2::Span;
+use std::collections::HashSet;
+use syn::spanned::Spanned;
 
-pub(crate) fn validate_option(meta: &OptionMeta, span: Span) -> Result<(), darling::Error> {
-    if meta.description.is_empty() {
-        return Err(syn::Error::new(span, "description is need for option.").into());
+const OPTION_LENGTH_LIMIT: u16 = 6000;
+pub(crate) const OPTION_LIMIT: usize = 25;
+
+/**
+Validate the name of chat input command, group and option.
+It must match `^[-_\p{L}\p{N}\p{sc=Deva}\p{sc=Thai}]{1,32}$` and must be lowercase.
+**/
+pub(crate) fn validate_name(name: &str, span: Span) -> Result<(), syn::Error> {
+    let length = name.chars().count();
+    if length == 0 || length > 32 {
+        return Err(syn::Error::new(
+            span,
+            format!("name `{}` length must be between 1 and 32", name),
+        ));
+    }
+    let is_allowed = |c: char| {
+        c == '-'
+            || c == '_'
+            || c.is_alphanumeric()
+            || ('\u{0900}'..='\u{097F}').contains(&c)
+            || ('\u{0E00}'..='\u{0E7F}').contains(&c)
+    };
+    if let Some(c) = name.chars().find(|c| !is_allowed(*c)) {
+        return Err(syn::Error::new(
+            span,
+            format!("name `{}` contains invalid character `{}`", name, c),
+        ));
     }
-    if meta.description.len() > 100 {
+    if name
+        .chars()
+        .any(|c| !c.to_lowercase().eq(std::iter::once(c)))
+    {
+        return Err(syn::Error::new(
+            span,
+            format!("name `{}` must be lowercase", name),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_description(description: &str, span: Span) -> Result<(), syn::Error> {
+    if description.is_empty() {
+        return Err(syn::Error::new(span, "description is need."));
+    }
+    if description.chars().count() > 100 {
         return Err(syn::Error::new(
             span,
             format!(
                 "description length is longer than limit ({} > 100)",
-                meta.description.len()
+                description.chars().count()
             ),
+        ));
+    }
+    Ok(())
+}
+
+/**
+Validate the options of a command: the count, the order of required options and the duplicated names.
+**/
+pub(crate) fn validate_options(options: &[&CommandOption], span: Span) -> Result<(), syn::Error> {
+    if options.len() > OPTION_LIMIT {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "command can't have more than {} options ({})",
+                OPTION_LIMIT,
+                options.len()
+            ),
+        ));
+    }
+    let mut names = HashSet::new();
+    let mut optional = false;
+    for option in options {
+        let name = option.option_name();
+        if !names.insert(name.clone()) {
+            return Err(syn::Error::new(
+                option.span,
+                format!("option name `{}` is duplicated", name),
+            ));
+        }
+        let (required, _) = parse_option_type(&option.t);
+        if required && optional {
+            return Err(syn::Error::new(
+                option.span,
+                format!(
+                    "required option `{}` must be placed before optional options",
+                    name
+                ),
+            ));
+        }
+        optional |= !required;
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_option(option: &CommandOption) -> Result<(), darling::Error> {
+    let meta = &option.meta;
+    let span = option.span;
+    validate_name(&option.option_name(), span)?;
+    validate_description(&meta.description, span)?;
+
+    let (_, ty) = parse_option_type(&option.t);
+    if (meta.min_value.is_some() || meta.max_value.is_some()) && !is_type(&ty, &["i64", "f64"]) {
+        return Err(syn::Error::new(
+            ty.span(),
+            "min_value and max_value are available only for i64 or f64 option",
+        )
+        .into());
+    }
+    if meta.min_length.is_some() || meta.max_length.is_some() {
+        if !is_type(&ty, &["String"]) {
+            return Err(syn::Error::new(
+                ty.span(),
+                "min_length and max_length are available only for String option",
+            )
+            .into());
+        }
+        let min_length = meta.min_length.unwrap_or(0);
+        let max_length = meta.max_length.unwrap_or(OPTION_LENGTH_LIMIT);
+        if max_length > OPTION_LENGTH_LIMIT || min_length > max_length {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "min_length and max_length must be 0 <= min_length <= max_length <= {}",
+                    OPTION_LENGTH_LIMIT
+                ),
+            )
+            .into());
+        }
+    }
+    if meta.channel_types.is_some() && !is_channel_type(&ty) {
+        return Err(syn::Error::new(
+            ty.span(),
+            "channel_types is available only for Id<ChannelMarker> or InteractionChannel option",
         )
         .into());
     }
-    // TODO: name validation
     Ok(())
 }
+
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    }
+}
+
+fn is_type(ty: &syn::Type, names: &[&str]) -> bool {
+    last_segment(ty)
+        .map(|segment| names.iter().any(|name| segment.ident == name))
+        .unwrap_or(false)
+}
+
+fn is_channel_type(ty: &syn::Type) -> bool {
+    let segment = match last_segment(ty) {
+        Some(x) => x,
+        None => return false,
+    };
+    if segment.ident == "InteractionChannel" {
+        return true;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Id" => {
+            matches!(args.args.first(), Some(syn::GenericArgument::Type(marker)) if is_type(marker, &["ChannelMarker"]))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        let span = Span::call_site();
+        assert!(validate_name("echo", span).is_ok());
+        assert!(validate_name("user-info_2", span).is_ok());
+        assert!(validate_name("おうむ返し", span).is_ok());
+        assert!(validate_name("", span).is_err());
+        assert!(validate_name("Echo", span).is_err());
+        assert!(validate_name("echo text", span).is_err());
+        assert!(validate_name(&"a".repeat(33), span).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+/*!
+Locales and translation lookup shared by the frameworks and their macros.
+*/
+use serde_json::Value;
+
+/**
+Call the macro with Discord locale codes and the names of their variants, like `"en-US" => EnUS`.
+
+https://discord.com/developers/docs/reference#locales
+**/
+#[macro_export]
+macro_rules! with_locales {
+    ($callback:ident) => {
+        $callback! {
+            "da" => Da,
+            "de" => De,
+            "en-GB" => EnGB,
+            "en-US" => EnUS,
+            "es-ES" => EsES,
+            "fr" => Fr,
+            "hr" => Hr,
+            "it" => It,
+            "lt" => Lt,
+            "hu" => Hu,
+            "nl" => Nl,
+            "no" => No,
+            "pl" => Pl,
+            "pt-BR" => PtBR,
+            "ro" => Ro,
+            "fi" => Fi,
+            "sv-SE" => SvSE,
+            "vi" => Vi,
+            "tr" => Tr,
+            "cs" => Cs,
+            "el" => El,
+            "bg" => Bg,
+            "ru" => Ru,
+            "uk" => Uk,
+            "hi" => Hi,
+            "th" => Th,
+            "zh-CN" => ZhCN,
+            "ja" => Ja,
+            "zh-TW" => ZhTW,
+            "ko" => Ko,
+        }
+    };
+}
+
+macro_rules! locale_table {
+    ($($code:literal => $variant:ident,)*) => {
+        &[$(($code, stringify!($variant)),)*]
+    };
+}
+
+/**
+Discord locale codes and the names of their variants.
+**/
+pub const LOCALES: &[(&str, &str)] = with_locales!(locale_table);
+
+/**
+Find the text by the dotted key. Both nested objects and flat keys are available.
+**/
+pub fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    if let Some(x) = value.get(key).and_then(Value::as_str) {
+        return Some(x);
+    }
+    match key.split_once('.') {
+        Some((head, rest)) => lookup(value.get(head)?, rest),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let value = serde_json::json!({
+            "commands": {"ban": {"name": "ban"}},
+            "responses.banned": "banned",
+        });
+        assert_eq!(lookup(&value, "commands.ban.name"), Some("ban"));
+        assert_eq!(lookup(&value, "responses.banned"), Some("banned"));
+        assert_eq!(lookup(&value, "commands.kick.name"), None);
+    }
+
+    #[test]
+    fn test_locales() {
+        assert_eq!(LOCALES.len(), 30);
+        assert!(LOCALES.contains(&("en-US", "EnUS")));
+    }
+}
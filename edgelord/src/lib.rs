@@ -1,4 +1,5 @@
 mod hook;
 pub mod http;
+pub mod i18n;
 
 pub use hook::set_panic_hook;
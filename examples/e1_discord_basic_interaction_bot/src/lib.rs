@@ -17,9 +17,9 @@ pub async fn fetch(req: Request, env: Env, worker_context: worker::Context) -> R
             let handler = InteractionHandler::builder()
                 .command(help_command())
                 .command(animal_image())
-                .public_key(&*env.secret("APPLICATION_PUBLIC_KEY")?.to_string())
-                .application_id(&*env.secret("APPLICATION_ID")?.to_string())
-                .token(&*env.secret("DISCORD_BOT_TOKEN")?.to_string())
+                .public_key(&env.secret("APPLICATION_PUBLIC_KEY")?.to_string())
+                .application_id(&env.secret("APPLICATION_ID")?.to_string())
+                .token(&env.secret("DISCORD_BOT_TOKEN")?.to_string())
                 .build()
                 .unwrap();
             handler.process(req, env, data).await
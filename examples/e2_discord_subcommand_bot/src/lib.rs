@@ -14,9 +14,9 @@ pub async fn fetch(req: Request, env: Env, worker_context: worker::Context) -> R
             let RouteContext { env, data, .. } = ctx;
             let handler = InteractionHandler::builder()
                 .group(emojis())
-                .public_key(&*env.secret("APPLICATION_PUBLIC_KEY")?.to_string())
-                .application_id(&*env.secret("APPLICATION_ID")?.to_string())
-                .token(&*env.secret("DISCORD_BOT_TOKEN")?.to_string())
+                .public_key(&env.secret("APPLICATION_PUBLIC_KEY")?.to_string())
+                .application_id(&env.secret("APPLICATION_ID")?.to_string())
+                .token(&env.secret("DISCORD_BOT_TOKEN")?.to_string())
                 .build()
                 .unwrap();
             handler.process(req, env, data).await
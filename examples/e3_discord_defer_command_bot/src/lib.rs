@@ -15,9 +15,9 @@ pub async fn fetch(req: Request, env: Env, worker_context: worker::Context) -> R
             let RouteContext { env, data, .. } = ctx;
             let handler = InteractionHandler::builder()
                 .command(send_lazy_message())
-                .public_key(&*env.secret("APPLICATION_PUBLIC_KEY")?.to_string())
-                .application_id(&*env.secret("APPLICATION_ID")?.to_string())
-                .token(&*env.secret("DISCORD_BOT_TOKEN")?.to_string())
+                .public_key(&env.secret("APPLICATION_PUBLIC_KEY")?.to_string())
+                .application_id(&env.secret("APPLICATION_ID")?.to_string())
+                .token(&env.secret("DISCORD_BOT_TOKEN")?.to_string())
                 .build()
                 .unwrap();
             handler.process(req, env, data).await
@@ -26,7 +26,10 @@ pub async fn fetch(req: Request, env: Env, worker_context: worker::Context) -> R
         .await
 }
 
-#[command(name = "lazy", description = "send message after 3s")]
+#[command(name = "lazy", description = "send message after deferring")]
 pub async fn send_lazy_message(ctx: ChatInputCommandContext) -> InteractionResponse {
-    ctx.defer(|msg| msg.flag(MessageFlags::EPHEMERAL))
+    ctx.defer_with_message(
+        |followup| async move { followup.edit(|msg| msg.content("done!")) },
+        |msg| msg.flag(MessageFlags::EPHEMERAL),
+    )
 }